@@ -1,88 +1,83 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    to_binary, AllBalanceResponse, BalanceResponse, BankMsg, BankQuery, Binary, Coin, Deps,
-    DepsMut, Env, MessageInfo, Response, StdResult,
+    to_binary, Addr, AllBalanceResponse, BalanceResponse, BankMsg, BankQuery, Binary, BlockInfo,
+    Coin, Deps, DepsMut, Empty, Env, Event, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, Storage, SubMsg, SubMsgResult, Uint128, WasmMsg,
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+use semver::Version;
 
 use crate::error::ContractError;
 use crate::helpers::{
-    create_denom_msg, is_contract_manager, is_whitelisted, mint_factory_token_messages,
-    mint_tokens_msg, pretty_denoms_output,
+    authorize_mint, build_new_denom, check_max_supply, ensure_denom_admin, ensure_managed_denom,
+    ensure_not_contract_funds, ensure_not_paused, ensure_transfer_allowed, get_mint_allowances,
+    has_role, is_contract_manager, is_whitelisted, load_minter, merge_coins,
+    mint_factory_token_messages, mint_tokens_msg, page_limit, pretty_denoms_output, record_burn,
+    record_mint, refund_mint_allowances, save_vesting, set_before_send_hook_msg, spend_allowance,
+    spend_burn_allowance, unrecord_mint, validate_managed_coin, validate_managed_coins,
+    validate_vesting_schedule, vested_amount,
+};
+use crate::migrations::migrate_state;
+use crate::msg::{
+    AllowanceInfo, AllowanceResponse, AuditEntryResponse, BurnAllowanceResponse, DenomInfoResponse,
+    ExecuteMsg, HookExecuteMsg, HookResponse, InstantiateMsg, MigrateMsg, MintAllowancesResponse,
+    MintBatchEntry, MintBatchMode, MintReceiveMsg, MintReceiverExecuteMsg, MinterResponse,
+    NewDenom, PauseStateResponse, PausedOperations, PendingActionResponse, ProposalResponse,
+    QueryMsg, Role, SudoMsg, TimelockResponse, TimelockedAction, TokenHookMsg, TokenOperation,
+    TotalsResponse, TransferPolicy, UncontrolledDenomResponse, VestingResponse, VestingSchedule,
+};
+use crate::state::{
+    Allowance, AuditEntry, BurnAllowance, Config, ForcedTransfer, HookSubscription, MinterGroup,
+    MinterInfo, PendingAction, PendingBatchMint, PendingManager, Proposal, Signers, Vesting,
+    ALLOWANCES, AUDIT_BY_ACTION, AUDIT_BY_DENOM, AUDIT_COUNT, AUDIT_LOG, BURNED, BURNED_BY,
+    BURN_ALLOWANCES, BURN_CONSENT_REQUIRED, CONFIG, DENOMS, DENOM_HOOKS, DENOM_PAUSE,
+    FORCED_TRANSFER, FROZEN, GLOBAL_HOOKS, GLOBAL_PAUSE, GUARDIAN, MAX_SUPPLY, MINTED, MINTED_BY,
+    MINTERS, MINTER_GROUP, MINT_ALLOWANCES, PENDING_ACTIONS, PENDING_ACTION_COUNT,
+    PENDING_BATCH_MINTS, PENDING_MANAGER, PROPOSALS, PROPOSAL_COUNT, RECIPIENT_VESTINGS, ROLES,
+    SIGNERS, TIMELOCKS, TRANSFER_ALLOWLIST, TRANSFER_POLICY, VESTINGS,
 };
-use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-use crate::state::{Config, CONFIG};
 
-use token_bindings::TokenFactoryMsg;
+use token_bindings::{TokenFactoryMsg, TokenFactoryQuery, TokenQuerier};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:tokenfactory-core";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+const MINT_BATCH_REPLY_ID: u64 = 1;
+const HOOK_REPLY_ID: u64 = 2;
+const FORCE_TRANSFER_REPLY_ID: u64 = 3;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
-    deps: DepsMut,
+    deps: DepsMut<TokenFactoryQuery>,
     env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response<TokenFactoryMsg>, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    // Validate existing denoms.
+    // Existing denoms must already have this contract as their admin.
     let mut denoms = msg.existing_denoms.unwrap_or_default();
     for d in denoms.iter() {
-        if !d.starts_with("factory/") {
-            return Err(ContractError::InvalidDenom {
-                denom: d.clone(),
-                message: "Denom must start with 'factory/'".to_string(),
-            });
-        }
+        ensure_denom_admin(deps.as_ref(), &env.contract.address, d)?;
     }
 
     // Create new denoms.
     let mut new_denom_msgs: Vec<TokenFactoryMsg> = vec![];
     let mut new_mint_msgs: Vec<TokenFactoryMsg> = vec![];
 
-    if let Some(new_denoms) = msg.new_denoms {
-        if !new_denoms.is_empty() {
-            for denom in new_denoms {
-                let subdenom = denom.symbol.to_lowercase();
-                let full_denom = format!("factory/{}/{}", env.contract.address, subdenom);
-
-                // Add creation message.
-                new_denom_msgs.push(create_denom_msg(
-                    subdenom.clone(),
-                    full_denom.clone(),
-                    denom.clone(),
-                ));
-
-                // Add initial balance mint messages.
-                if let Some(initial_balances) = denom.initial_balances {
-                    if !initial_balances.is_empty() {
-                        // Validate addresses.
-                        for initial in initial_balances.iter() {
-                            deps.api.addr_validate(&initial.address)?;
-                        }
-
-                        for b in initial_balances {
-                            new_mint_msgs.push(mint_tokens_msg(
-                                b.address.clone(),
-                                full_denom.clone(),
-                                b.amount,
-                            ));
-                        }
-                    }
-                }
+    for denom in msg.new_denoms.unwrap_or_default() {
+        let (full_denom, create_msg, mint_msgs) =
+            build_new_denom(deps.storage, deps.api, &env.contract.address, denom)?;
 
-                // Add to existing denoms.
-                denoms.push(full_denom);
-            }
-        }
-    }
+        new_denom_msgs.push(create_msg);
+        new_mint_msgs.extend(mint_msgs);
 
-    if denoms.is_empty() {
-        return Err(ContractError::NoDenomsProvided {});
+        // Add to existing denoms.
+        denoms.push(full_denom);
     }
 
     let manager = deps
@@ -90,12 +85,17 @@ pub fn instantiate(
         .addr_validate(&msg.manager.unwrap_or_else(|| _info.sender.to_string()))?;
 
     let config = Config {
-        manager: manager.to_string(),
-        allowed_mint_addresses: msg.allowed_mint_addresses,
-        denoms,
+        manager: Some(manager.to_string()),
     };
     CONFIG.save(deps.storage, &config)?;
 
+    for address in msg.allowed_mint_addresses.iter() {
+        MINTERS.save(deps.storage, address, &MinterInfo { denoms: None })?;
+    }
+    for denom in denoms.iter() {
+        DENOMS.save(deps.storage, denom, &Empty {})?;
+    }
+
     Ok(Response::new()
         .add_attribute("method", "instantiate")
         .add_messages(new_denom_msgs)
@@ -104,76 +104,217 @@ pub fn instantiate(
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    deps: DepsMut<TokenFactoryQuery>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    // Logged up front, so queued actions are logged when requested. An action which fails
+    // reverts its entry along with everything else.
+    record_audit(deps.storage, &env, &info.sender, &msg, None)?;
+
+    // Timelocked actions are authorized now, but only queued to run once their delay passes.
+    // Burns the holder allowed the sender to make are not delayed.
+    if let Some(action) = timelocked_action(&msg) {
+        let consented = match &msg {
+            ExecuteMsg::BurnFrom { from, denom, .. } => BURN_ALLOWANCES.has(
+                deps.storage,
+                (from.as_str(), denom.denom.as_str(), info.sender.as_str()),
+            ),
+            _ => false,
+        };
+        if !consented {
+            if let Some(delay) = TIMELOCKS.may_load(deps.storage, action.as_str())? {
+                return queue_action(deps, env, info, action, delay, msg);
+            }
+        }
+    }
+
     match msg {
         // == ANYONE ==
         ExecuteMsg::Burn {} => execute_burn(deps, env, info),
+        ExecuteMsg::ClaimVested { ids } => execute_claim_vested(deps, env, info, ids),
+        ExecuteMsg::ExecutePendingAction { id } => execute_pending_action(deps, env, id),
+        ExecuteMsg::SetBurnAllowance {
+            denom,
+            burner,
+            amount,
+            expires,
+        } => {
+            ensure_managed_denom(deps.storage, &denom)?;
+            if let Some(expires) = expires {
+                if expires.is_expired(&env.block) {
+                    return Err(ContractError::InvalidExpiration {});
+                }
+            }
+            let burner = match burner {
+                Some(burner) => deps.api.addr_validate(&burner)?,
+                None => env.contract.address.clone(),
+            };
 
-        // == WHITELIST ==
-        ExecuteMsg::Mint { address, denom } => execute_mint(deps, info, address, denom),
-
-        // == MANAGER ==
-        ExecuteMsg::BurnFrom { from, denom } => {
-            let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config, info.sender)?;
-
-            let balance = deps.querier.query_all_balances(from.clone())?;
+            BURN_ALLOWANCES.save(
+                deps.storage,
+                (info.sender.as_str(), &denom, burner.as_str()),
+                &BurnAllowance { amount, expires },
+            )?;
 
-            let mut found = false;
-            for coin in balance.iter() {
-                if coin.denom == denom.denom {
-                    found = true;
+            Ok(Response::new()
+                .add_attribute("method", "set_burn_allowance")
+                .add_attribute("holder", info.sender)
+                .add_attribute("denom", denom)
+                .add_attribute("burner", burner)
+                .add_attribute("amount", amount))
+        }
+        ExecuteMsg::IncreaseAllowance {
+            spender,
+            denom,
+            expires,
+        } => {
+            let spender = deps.api.addr_validate(&spender)?;
+            if spender == info.sender {
+                return Err(ContractError::CannotSetOwnAccount {});
+            }
+            ensure_not_contract_funds(&env.contract.address, info.sender.as_str())?;
+            validate_managed_coin(deps.storage, &denom)?;
+            if let Some(expires) = expires {
+                if expires.is_expired(&env.block) {
+                    return Err(ContractError::InvalidExpiration {});
                 }
             }
 
-            if !found {
-                return Err(ContractError::InvalidDenom {
-                    denom: denom.denom,
-                    message: "Denom not found in balance".to_string(),
+            let key = (info.sender.as_str(), denom.denom.as_str(), spender.as_str());
+            let mut allowance = ALLOWANCES
+                .may_load(deps.storage, key)?
+                .unwrap_or(Allowance {
+                    amount: Uint128::zero(),
+                    expires: None,
                 });
+            allowance.amount = allowance
+                .amount
+                .checked_add(denom.amount)
+                .map_err(StdError::from)?;
+            if expires.is_some() {
+                allowance.expires = expires;
             }
+            ALLOWANCES.save(deps.storage, key, &allowance)?;
 
-            // burn from from_address
-            let msg: TokenFactoryMsg = TokenFactoryMsg::BurnTokens {
-                denom: denom.denom.clone(),
-                amount: denom.amount,
-                burn_from_address: from,
-            };
+            Ok(Response::new()
+                .add_attribute("method", "increase_allowance")
+                .add_attribute("owner", info.sender)
+                .add_attribute("spender", spender)
+                .add_attribute("denom", denom.denom)
+                .add_attribute("amount", denom.amount))
+        }
+        ExecuteMsg::DecreaseAllowance {
+            spender,
+            denom,
+            expires,
+        } => {
+            let spender = deps.api.addr_validate(&spender)?;
+            if spender == info.sender {
+                return Err(ContractError::CannotSetOwnAccount {});
+            }
+
+            let key = (info.sender.as_str(), denom.denom.as_str(), spender.as_str());
+            let mut allowance = ALLOWANCES.may_load(deps.storage, key)?.ok_or_else(|| {
+                ContractError::NoAllowance {
+                    owner: info.sender.to_string(),
+                    denom: denom.denom.clone(),
+                }
+            })?;
+            // Decreasing by the whole allowance or more removes it
+            if denom.amount >= allowance.amount {
+                ALLOWANCES.remove(deps.storage, key);
+            } else {
+                if let Some(expires) = expires {
+                    if expires.is_expired(&env.block) {
+                        return Err(ContractError::InvalidExpiration {});
+                    }
+                    allowance.expires = Some(expires);
+                }
+                allowance.amount -= denom.amount;
+                ALLOWANCES.save(deps.storage, key, &allowance)?;
+            }
 
             Ok(Response::new()
-                .add_attribute("method", "execute_burn_from")
+                .add_attribute("method", "decrease_allowance")
+                .add_attribute("owner", info.sender)
+                .add_attribute("spender", spender)
                 .add_attribute("denom", denom.denom)
-                .add_message(msg))
+                .add_attribute("amount", denom.amount))
         }
+        ExecuteMsg::TransferFrom {
+            owner,
+            recipient,
+            denom,
+        } => execute_transfer_from(deps, env, info.sender, owner, recipient, denom),
+        ExecuteMsg::RevokeBurnAllowance { denom, burner } => {
+            let burner = match burner {
+                Some(burner) => deps.api.addr_validate(&burner)?,
+                None => env.contract.address.clone(),
+            };
+            BURN_ALLOWANCES.remove(
+                deps.storage,
+                (info.sender.as_str(), &denom, burner.as_str()),
+            );
 
-        ExecuteMsg::TransferAdmin { denom, new_address } => {
-            execute_transfer_admin(deps, info, denom, new_address)
+            Ok(Response::new()
+                .add_attribute("method", "revoke_burn_allowance")
+                .add_attribute("holder", info.sender)
+                .add_attribute("denom", denom)
+                .add_attribute("burner", burner))
         }
 
-        ExecuteMsg::ForceTransfer { from, to, denom } => {
+        // == WHITELIST ==
+        ExecuteMsg::Mint { address, denom } => execute_mint(deps, env, info, address, denom),
+        ExecuteMsg::MintAndCall {
+            contract,
+            denom,
+            msg,
+        } => execute_mint_and_call(deps, env, info, contract, denom, msg),
+        ExecuteMsg::MintBatch { mints, mode } => execute_mint_batch(
+            deps,
+            env,
+            info,
+            mints,
+            mode.unwrap_or(MintBatchMode::AllOrNothing),
+        ),
+        ExecuteMsg::MintVesting {
+            recipient,
+            denom,
+            schedule,
+        } => execute_mint_vesting(deps, env, info, recipient, denom, schedule),
+
+        // == MANAGER ==
+        ExecuteMsg::BurnFrom { from, denom, .. } => {
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config, info.sender)?;
+            // Burners holding an allowance from the holder do not need the role.
+            let is_burner = has_role(deps.storage, &config, Role::Burner, &info.sender).is_ok();
+            execute_burn_from(deps, env, info.sender, is_burner, from, denom)
+        }
 
-            let msg: TokenFactoryMsg = TokenFactoryMsg::ForceTransfer {
-                denom: denom.denom.clone(),
-                amount: denom.amount,
-                from_address: from,
-                to_address: to,
-            };
+        ExecuteMsg::TransferAdmin {
+            denom, new_address, ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            execute_transfer_admin(deps, denom, new_address)
+        }
 
-            Ok(Response::new()
-                .add_attribute("method", "execute_force_transfer")
-                .add_attribute("denom", denom.denom)
-                .add_message(msg))
+        ExecuteMsg::ForceTransfer {
+            from, to, denom, ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::ClawbackOperator, &info.sender)?;
+            execute_force_transfer(deps, env, info.sender, from, to, denom)
         }
 
-        ExecuteMsg::SetMetadata { denom, metadata } => {
+        ExecuteMsg::SetMetadata {
+            denom, metadata, ..
+        } => {
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config, info.sender)?;
+            has_role(deps.storage, &config, Role::MetadataEditor, &info.sender)?;
+            ensure_managed_denom(deps.storage, &denom)?;
 
             let msg: TokenFactoryMsg = TokenFactoryMsg::SetMetadata {
                 denom: denom.clone(),
@@ -187,192 +328,3736 @@ pub fn execute(
         }
 
         // Merge these into a modify whitelist
-        ExecuteMsg::AddWhitelist { addresses } => {
+        ExecuteMsg::AddWhitelist {
+            addresses, denoms, ..
+        } => {
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config.clone(), info.sender)?;
+            has_role(deps.storage, &config, Role::MinterAdmin, &info.sender)?;
 
-            // add addresses if it is not in config.allowed_mint_addresses
-            let mut updated = config.allowed_mint_addresses;
-            for new in addresses {
-                if !updated.contains(&new) {
-                    updated.push(new);
-                }
+            for denom in denoms.iter().flatten() {
+                ensure_managed_denom(deps.storage, denom)?;
             }
 
-            CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-                config.allowed_mint_addresses = updated;
-                Ok(config)
-            })?;
+            // adding an existing address replaces its denoms
+            let minter = MinterInfo { denoms };
+            for new in addresses {
+                MINTERS.save(deps.storage, &new, &minter)?;
+            }
 
             Ok(Response::new().add_attribute("method", "add_whitelist"))
         }
-        ExecuteMsg::RemoveWhitelist { addresses } => {
+        ExecuteMsg::RemoveWhitelist { addresses, .. } => {
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config.clone(), info.sender)?;
+            has_role(deps.storage, &config, Role::MinterAdmin, &info.sender)?;
 
-            let mut updated = config.allowed_mint_addresses;
             for remove in addresses {
-                updated.retain(|a| a != &remove);
+                MINTERS.remove(deps.storage, &remove);
+
+                // stale allowances would apply again if the address is re-added later
+                let allowance_denoms = MINT_ALLOWANCES
+                    .prefix(&remove)
+                    .keys(deps.storage, None, None, Order::Ascending)
+                    .collect::<StdResult<Vec<String>>>()?;
+                for denom in allowance_denoms {
+                    MINT_ALLOWANCES.remove(deps.storage, (&remove, &denom));
+                }
             }
 
-            CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-                config.allowed_mint_addresses = updated;
-                Ok(config)
-            })?;
             Ok(Response::new().add_attribute("method", "remove_whitelist"))
         }
 
-        ExecuteMsg::AddDenom { denoms } => {
+        ExecuteMsg::SetMinterGroup {
+            address,
+            min_weight,
+            ..
+        } => {
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config.clone(), info.sender)?;
+            has_role(deps.storage, &config, Role::MinterAdmin, &info.sender)?;
 
-            let mut updated_denoms = config.denoms;
-            for new in denoms {
-                if !updated_denoms.contains(&new) {
-                    updated_denoms.push(new);
+            match address.as_ref() {
+                Some(address) => {
+                    let address = deps.api.addr_validate(address)?;
+                    MINTER_GROUP.save(
+                        deps.storage,
+                        &MinterGroup {
+                            address: address.to_string(),
+                            min_weight,
+                        },
+                    )?;
                 }
+                None => MINTER_GROUP.remove(deps.storage),
             }
 
-            CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-                config.denoms = updated_denoms;
-                Ok(config)
-            })?;
+            Ok(Response::new()
+                .add_attribute("method", "set_minter_group")
+                .add_attribute("group", address.unwrap_or_default())
+                .add_attribute("min_weight", min_weight.unwrap_or_default().to_string()))
+        }
+
+        ExecuteMsg::SetMintAllowances {
+            address,
+            allowances,
+            ..
+        } => execute_set_mint_allowances(deps, info, address, allowances),
+        ExecuteMsg::RemoveMintAllowances {
+            address,
+            denoms,
+            unlimited,
+            ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::MinterAdmin, &info.sender)?;
+
+            // A missing allowance means no limit, so lifting the limit has to be asked for.
+            for denom in denoms.iter() {
+                if unlimited {
+                    MINT_ALLOWANCES.remove(deps.storage, (&address, denom));
+                } else {
+                    MINT_ALLOWANCES.save(deps.storage, (&address, denom), &Uint128::zero())?;
+                }
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "remove_mint_allowances")
+                .add_attribute("address", address)
+                .add_attribute("unlimited", unlimited.to_string()))
+        }
+
+        ExecuteMsg::AddDenom { denoms, .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+
+            for new in denoms {
+                ensure_denom_admin(deps.as_ref(), &env.contract.address, &new)?;
+                DENOMS.save(deps.storage, &new, &Empty {})?;
+            }
 
             Ok(Response::new().add_attribute("method", "add_denom"))
         }
-        ExecuteMsg::RemoveDenom { denoms } => {
+        ExecuteMsg::RemoveDenom { denoms, .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            execute_remove_denom(deps, denoms)
+        }
+
+        ExecuteMsg::CreateDenom { denom, .. } => execute_create_denom(deps, env, info, denom),
+
+        ExecuteMsg::SetMaxSupply {
+            denom, max_supply, ..
+        } => execute_set_max_supply(deps, info, denom, max_supply),
+
+        ExecuteMsg::SetPaused { denom, paused, .. } => {
+            execute_set_paused(deps, info, denom, paused)
+        }
+
+        ExecuteMsg::Freeze {
+            denom, addresses, ..
+        } => execute_set_frozen(deps, info, denom, addresses, true),
+        ExecuteMsg::Unfreeze {
+            denom, addresses, ..
+        } => execute_set_frozen(deps, info, denom, addresses, false),
+        ExecuteMsg::SetBurnConsentRequired {
+            denom, required, ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            ensure_managed_denom(deps.storage, &denom)?;
+
+            // One way, so holders can rely on it once it is on.
+            if required {
+                BURN_CONSENT_REQUIRED.save(deps.storage, &denom, &Empty {})?;
+            } else if BURN_CONSENT_REQUIRED.has(deps.storage, &denom) {
+                return Err(ContractError::BurnConsentLocked { denom });
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "set_burn_consent_required")
+                .add_attribute("denom", denom)
+                .add_attribute("required", required.to_string()))
+        }
+        ExecuteMsg::AddHook {
+            contract,
+            denom,
+            tolerate_errors,
+            ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            let contract = deps.api.addr_validate(&contract)?;
+
+            let subscription = HookSubscription { tolerate_errors };
+            match denom.as_ref() {
+                Some(denom) => {
+                    ensure_managed_denom(deps.storage, denom)?;
+                    DENOM_HOOKS.save(deps.storage, (denom, contract.as_str()), &subscription)?;
+                }
+                None => GLOBAL_HOOKS.save(deps.storage, contract.as_str(), &subscription)?,
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "add_hook")
+                .add_attribute("contract", contract)
+                .add_attribute("denom", denom.unwrap_or_default())
+                .add_attribute("tolerate_errors", tolerate_errors.to_string()))
+        }
+        ExecuteMsg::RemoveHook {
+            contract, denom, ..
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+
+            match denom.as_ref() {
+                Some(denom) => DENOM_HOOKS.remove(deps.storage, (denom, &contract)),
+                None => GLOBAL_HOOKS.remove(deps.storage, &contract),
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "remove_hook")
+                .add_attribute("contract", contract)
+                .add_attribute("denom", denom.unwrap_or_default()))
+        }
+        ExecuteMsg::SetTransferPolicy { denom, policy, .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            ensure_managed_denom(deps.storage, &denom)?;
+
+            let previous = TRANSFER_POLICY
+                .may_load(deps.storage, &denom)?
+                .unwrap_or(TransferPolicy::Open);
+            TRANSFER_POLICY.save(deps.storage, &denom, &policy)?;
+
+            let event = Event::new("transfer_policy_changed")
+                .add_attribute("denom", &denom)
+                .add_attribute("previous", previous.as_str())
+                .add_attribute("policy", policy.as_str());
+
+            Ok(Response::new()
+                .add_attribute("method", "set_transfer_policy")
+                .add_attribute("denom", denom)
+                .add_attribute("policy", policy.as_str())
+                .add_event(event))
+        }
+        ExecuteMsg::AddTransferAllowlist {
+            denom, addresses, ..
+        } => execute_modify_transfer_allowlist(deps, info, denom, addresses, true),
+        ExecuteMsg::RemoveTransferAllowlist {
+            denom, addresses, ..
+        } => execute_modify_transfer_allowlist(deps, info, denom, addresses, false),
+        ExecuteMsg::SetBeforeSendHook { denom, enabled, .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            ensure_managed_denom(deps.storage, &denom)?;
+
+            let hook = if enabled {
+                env.contract.address.to_string()
+            } else {
+                String::new()
+            };
+            let msg = set_before_send_hook_msg(&env.contract.address, &denom, &hook);
+
+            Ok(Response::new()
+                .add_attribute("method", "set_before_send_hook")
+                .add_attribute("denom", denom)
+                .add_attribute("enabled", enabled.to_string())
+                .add_message(msg))
+        }
+
+        ExecuteMsg::GrantRole {
+            role, addresses, ..
+        } => execute_modify_role(deps, info, role, addresses, true),
+        ExecuteMsg::RevokeRole {
+            role, addresses, ..
+        } => execute_modify_role(deps, info, role, addresses, false),
+
+        ExecuteMsg::RevokeVesting { id, .. } => execute_revoke_vesting(deps, env, info, id),
+
+        ExecuteMsg::ProposeManager {
+            address, expiry, ..
+        } => execute_propose_manager(deps, env, info, address, expiry),
+        ExecuteMsg::CancelManagerProposal { .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            is_contract_manager(config, info.sender)?;
+
+            if !PENDING_MANAGER.exists(deps.storage) {
+                return Err(ContractError::NoPendingManager {});
+            }
+            PENDING_MANAGER.remove(deps.storage);
+
+            Ok(Response::new().add_attribute("method", "cancel_manager_proposal"))
+        }
+        ExecuteMsg::SetTimelock {
+            action,
+            delay,
+            reason,
+        } => {
+            let config = CONFIG.load(deps.storage)?;
+            is_contract_manager(config, info.sender.clone())?;
+
+            // Lowering or removing a delay waits out the current one, so it can not be skipped.
+            if let Some(current) = TIMELOCKS.may_load(deps.storage, action.as_str())? {
+                if delay.unwrap_or_default() < current {
+                    let msg = ExecuteMsg::SetTimelock {
+                        action,
+                        delay,
+                        reason,
+                    };
+                    return save_pending_action(
+                        deps.storage,
+                        &env,
+                        &info.sender,
+                        "set_timelock",
+                        current,
+                        msg,
+                    );
+                }
+            }
+
+            execute_set_timelock(deps, action, delay)
+        }
+        ExecuteMsg::SetGuardian { address, .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            is_contract_manager(config, info.sender)?;
+
+            match address.as_ref() {
+                Some(address) => {
+                    let address = deps.api.addr_validate(address)?;
+                    GUARDIAN.save(deps.storage, &address.to_string())?;
+                }
+                None => GUARDIAN.remove(deps.storage),
+            }
+
+            Ok(Response::new()
+                .add_attribute("method", "set_guardian")
+                .add_attribute("guardian", address.unwrap_or_default()))
+        }
+        ExecuteMsg::CancelPendingAction { id, .. } => {
+            // The manager or the guardian can cancel
             let config = CONFIG.load(deps.storage)?;
-            is_contract_manager(config.clone(), info.sender)?;
+            let guardian = GUARDIAN.may_load(deps.storage)?;
+            if guardian.as_deref() != Some(info.sender.as_str()) {
+                is_contract_manager(config, info.sender)?;
+            }
 
-            let mut updated_denoms = config.denoms;
-            for remove in denoms {
-                updated_denoms.retain(|a| a != &remove);
+            if !PENDING_ACTIONS.has(deps.storage, id) {
+                return Err(ContractError::PendingActionNotFound { id });
             }
+            PENDING_ACTIONS.remove(deps.storage, id);
+
+            Ok(Response::new()
+                .add_attribute("method", "cancel_pending_action")
+                .add_attribute("id", id.to_string()))
+        }
+        ExecuteMsg::SetSigners {
+            signers,
+            threshold,
+            proposal_duration,
+            ..
+        } => execute_set_signers(deps, env, info, signers, threshold, proposal_duration),
+        ExecuteMsg::RenounceManager { .. } => {
+            let config = CONFIG.load(deps.storage)?;
+            is_contract_manager(config, info.sender)?;
 
+            PENDING_MANAGER.remove(deps.storage);
+            SIGNERS.remove(deps.storage);
             CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-                config.denoms = updated_denoms;
+                config.manager = None;
                 Ok(config)
             })?;
-            Ok(Response::new().add_attribute("method", "remove_denom"))
+
+            // Granted roles would otherwise keep the manager actions alive.
+            let roles = ROLES
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<_>>>()?;
+            for (role, address) in roles.iter() {
+                ROLES.remove(deps.storage, (role, address));
+            }
+
+            Ok(Response::new().add_attribute("method", "renounce_manager"))
         }
+
+        // == SIGNERS ==
+        ExecuteMsg::Propose { msg } => execute_propose(deps, env, info, *msg),
+        ExecuteMsg::ApproveProposal { id } => execute_approve_proposal(deps, env, info, id),
+
+        // == PENDING MANAGER ==
+        ExecuteMsg::AcceptManager {} => execute_accept_manager(deps, env, info),
     }
 }
 
-pub fn execute_transfer_admin(
-    deps: DepsMut,
+pub fn execute_modify_role(
+    deps: DepsMut<TokenFactoryQuery>,
     info: MessageInfo,
-    denom: String,
-    new_addr: String,
+    role: Role,
+    addresses: Vec<String>,
+    grant: bool,
 ) -> Result<Response<TokenFactoryMsg>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    is_contract_manager(config.clone(), info.sender)?;
-
-    // it is possible to transfer admin in without adding to contract config. So devs need a way to reclaim admin without adding it to denoms config
-    let config_denom: Option<&String> = config.denoms.iter().find(|d| d.to_string() == denom);
 
-    if let Some(config_denom) = config_denom {
-        // remove it from config
-        let updated_config: Vec<String> = config
-            .denoms
-            .iter()
-            .filter(|d| d.to_string() != *config_denom)
-            .map(|d| d.to_string())
-            .collect();
+    // super-admins can hand out every other role, but not their own
+    if role == Role::SuperAdmin {
+        is_contract_manager(config, info.sender)?;
+    } else {
+        has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+    }
 
-        CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
-            config.denoms = updated_config;
-            Ok(config)
-        })?;
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if grant {
+            ROLES.save(deps.storage, (role.as_str(), address.as_str()), &Empty {})?;
+        } else {
+            ROLES.remove(deps.storage, (role.as_str(), address.as_str()));
+        }
     }
 
-    let msg = TokenFactoryMsg::ChangeAdmin {
-        denom: denom.to_string(),
-        new_admin_address: new_addr.to_string(),
+    Ok(Response::new()
+        .add_attribute("method", if grant { "grant_role" } else { "revoke_role" })
+        .add_attribute("role", role.as_str())
+        .add_attribute("addresses", addresses.join(", ")))
+}
+
+pub fn execute_set_paused(
+    deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    denom: Option<String>,
+    paused: PausedOperations,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::Pauser, &info.sender)?;
+
+    let scope = match denom {
+        Some(denom) => {
+            ensure_managed_denom(deps.storage, &denom)?;
+
+            if paused == PausedOperations::default() {
+                DENOM_PAUSE.remove(deps.storage, &denom);
+            } else {
+                DENOM_PAUSE.save(deps.storage, &denom, &paused)?;
+            }
+            denom
+        }
+        None => {
+            GLOBAL_PAUSE.save(deps.storage, &paused)?;
+            "global".to_string()
+        }
     };
 
     Ok(Response::new()
-        .add_attribute("method", "execute_transfer_admin")
-        .add_attribute("new_admin", new_addr)
-        .add_message(msg))
+        .add_attribute("method", "set_paused")
+        .add_attribute("scope", scope)
+        .add_attribute("mint", paused.mint.to_string())
+        .add_attribute("burn", paused.burn.to_string())
+        .add_attribute("burn_from", paused.burn_from.to_string())
+        .add_attribute("force_transfer", paused.force_transfer.to_string())
+        .add_attribute("transfer_from", paused.transfer_from.to_string()))
 }
 
-pub fn execute_mint(
-    deps: DepsMut,
+pub fn execute_propose_manager(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
     info: MessageInfo,
     address: String,
-    denoms: Vec<Coin>,
+    expiry: Option<Expiration>,
 ) -> Result<Response<TokenFactoryMsg>, ContractError> {
     let config = CONFIG.load(deps.storage)?;
+    is_contract_manager(config, info.sender)?;
 
-    is_whitelisted(config, info.sender)?;
+    let address = deps.api.addr_validate(&address)?.to_string();
 
-    let mint_msgs: Vec<TokenFactoryMsg> = mint_factory_token_messages(&address, &denoms)?;
+    if let Some(expiry) = expiry {
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::ManagerProposalExpired {});
+        }
+    }
+
+    PENDING_MANAGER.save(
+        deps.storage,
+        &PendingManager {
+            address: address.clone(),
+            expiry,
+        },
+    )?;
 
     Ok(Response::new()
-        .add_attribute("method", "execute_mint")
-        .add_attribute("to_address", address)
-        .add_attribute("denoms", pretty_denoms_output(&denoms))
-        .add_messages(mint_msgs))
+        .add_attribute("method", "propose_manager")
+        .add_attribute("pending_manager", address))
 }
 
-pub fn execute_burn(
-    deps: DepsMut,
+pub fn execute_accept_manager(
+    deps: DepsMut<TokenFactoryQuery>,
     env: Env,
     info: MessageInfo,
 ) -> Result<Response<TokenFactoryMsg>, ContractError> {
-    // Anyone can burn funds since they have to send them in.
-    if info.funds.is_empty() {
-        return Err(ContractError::InvalidFunds {});
-    }
-
-    let config = CONFIG.load(deps.storage)?;
+    let pending = PENDING_MANAGER
+        .may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingManager {})?;
 
-    let (factory_denoms, send_back): (Vec<Coin>, Vec<Coin>) = info
-        .funds
-        .iter()
-        .cloned()
-        .partition(|coin| config.denoms.iter().any(|d| *d == coin.denom));
+    if pending.address != info.sender.as_str() {
+        return Err(ContractError::Unauthorized {});
+    }
 
-    let burn_msgs: Vec<TokenFactoryMsg> = factory_denoms
-        .iter()
-        .map(|coin| TokenFactoryMsg::BurnTokens {
-            denom: coin.denom.clone(),
-            amount: coin.amount,
-            burn_from_address: env.contract.address.to_string(),
-        })
-        .collect();
+    if let Some(expiry) = pending.expiry {
+        if expiry.is_expired(&env.block) {
+            return Err(ContractError::ManagerProposalExpired {});
+        }
+    }
 
-    let bank_return_msg = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: send_back,
-    };
+    PENDING_MANAGER.remove(deps.storage);
+    SIGNERS.remove(deps.storage);
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.manager = Some(pending.address.clone());
+        Ok(config)
+    })?;
 
     Ok(Response::new()
-        .add_attribute("method", "execute_burn")
-        .add_message(bank_return_msg)
-        .add_messages(burn_msgs))
+        .add_attribute("method", "accept_manager")
+        .add_attribute("manager", pending.address))
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::GetConfig {} => {
-            let config = CONFIG.load(deps.storage)?;
-            to_binary(&config)
-        }
-        QueryMsg::GetBalance { address, denom } => {
-            let v = BankQuery::Balance { address, denom };
-            let res: BalanceResponse = deps.querier.query(&v.into())?;
-            to_binary(&res.amount)
+pub fn execute_set_frozen(
+    deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    denom: String,
+    addresses: Vec<String>,
+    frozen: bool,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::Freezer, &info.sender)?;
+    ensure_managed_denom(deps.storage, &denom)?;
+
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if frozen {
+            FROZEN.save(deps.storage, (&denom, address.as_str()), &Empty {})?;
+        } else {
+            FROZEN.remove(deps.storage, (&denom, address.as_str()));
         }
+    }
 
-        // Since RPC's do not like to return factory/ denoms. We allow that through this query
-        QueryMsg::GetAllBalances { address } => {
-            let v = BankQuery::AllBalances { address };
+    Ok(Response::new()
+        .add_attribute("method", if frozen { "freeze" } else { "unfreeze" })
+        .add_attribute("denom", denom)
+        .add_attribute("addresses", addresses.join(",")))
+}
 
-            let res: AllBalanceResponse = deps.querier.query(&v.into())?;
+pub fn execute_modify_transfer_allowlist(
+    deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    denom: String,
+    addresses: Vec<String>,
+    add: bool,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+    ensure_managed_denom(deps.storage, &denom)?;
 
-            to_binary(&res.amount)
+    for address in addresses.iter() {
+        let address = deps.api.addr_validate(address)?;
+        if add {
+            TRANSFER_ALLOWLIST.save(deps.storage, (&denom, address.as_str()), &Empty {})?;
+        } else {
+            TRANSFER_ALLOWLIST.remove(deps.storage, (&denom, address.as_str()));
         }
     }
+
+    let method = if add {
+        "add_transfer_allowlist"
+    } else {
+        "remove_transfer_allowlist"
+    };
+    Ok(Response::new()
+        .add_attribute("method", method)
+        .add_attribute("denom", denom)
+        .add_attribute("addresses", addresses.join(",")))
+}
+
+pub fn execute_set_signers(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    signers: Vec<String>,
+    threshold: u32,
+    proposal_duration: u64,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    is_contract_manager(config, info.sender)?;
+
+    let mut addresses: Vec<String> = vec![];
+    for signer in signers {
+        let signer = deps.api.addr_validate(&signer)?.to_string();
+        if !addresses.contains(&signer) {
+            addresses.push(signer);
+        }
+    }
+    if threshold == 0 || threshold as usize > addresses.len() {
+        return Err(ContractError::InvalidThreshold {});
+    }
+
+    SIGNERS.save(
+        deps.storage,
+        &Signers {
+            addresses: addresses.clone(),
+            threshold,
+            proposal_duration,
+        },
+    )?;
+
+    // Manager actions now only run through approved proposals, which the contract sends itself.
+    PENDING_MANAGER.remove(deps.storage);
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.manager = Some(env.contract.address.to_string());
+        Ok(config)
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("method", "set_signers")
+        .add_attribute("signers", addresses.join(","))
+        .add_attribute("threshold", threshold.to_string()))
+}
+
+pub fn execute_propose(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let signers = SIGNERS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::SignersNotEnabled {})?;
+    if !signers.addresses.contains(&info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let id = PROPOSAL_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    PROPOSAL_COUNT.save(deps.storage, &id)?;
+
+    let proposal = Proposal {
+        msg,
+        proposer: info.sender.to_string(),
+        approvals: vec![info.sender.to_string()],
+        expires: env.block.time.plus_seconds(signers.proposal_duration),
+    };
+
+    let res = Response::new()
+        .add_attribute("method", "propose")
+        .add_attribute("id", id.to_string())
+        .add_attribute("proposer", info.sender);
+
+    run_or_save_proposal(deps, env, &signers, id, proposal, res)
+}
+
+pub fn execute_approve_proposal(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let signers = SIGNERS
+        .may_load(deps.storage)?
+        .ok_or(ContractError::SignersNotEnabled {})?;
+    if !signers.addresses.contains(&info.sender.to_string()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut proposal = PROPOSALS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::ProposalNotFound { id })?;
+    if env.block.time >= proposal.expires {
+        return Err(ContractError::ProposalExpired { id });
+    }
+    if proposal.approvals.contains(&info.sender.to_string()) {
+        return Err(ContractError::AlreadyApproved { id });
+    }
+    proposal.approvals.push(info.sender.to_string());
+
+    let res = Response::new()
+        .add_attribute("method", "approve_proposal")
+        .add_attribute("id", id.to_string())
+        .add_attribute("signer", info.sender);
+
+    run_or_save_proposal(deps, env, &signers, id, proposal, res)
+}
+
+/// Once enough current signers have approved, the proposal is removed and the contract
+/// executes its message on itself, as the manager. Otherwise the proposal is saved.
+fn run_or_save_proposal(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    signers: &Signers,
+    id: u64,
+    proposal: Proposal,
+    res: Response<TokenFactoryMsg>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let approvals = proposal
+        .approvals
+        .iter()
+        .filter(|a| signers.addresses.contains(*a))
+        .count();
+
+    if approvals < signers.threshold as usize {
+        PROPOSALS.save(deps.storage, id, &proposal)?;
+        return Ok(res.add_attribute("approvals", approvals.to_string()));
+    }
+
+    PROPOSALS.remove(deps.storage, id);
+
+    let msg = WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&proposal.msg)?,
+        funds: vec![],
+    };
+
+    Ok(res
+        .add_attribute("approvals", approvals.to_string())
+        .add_attribute("executed", "true")
+        .add_message(msg))
+}
+
+pub fn execute_create_denom(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    denom: NewDenom,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+
+    // The module takes the creation fee from this contract, so the sender has to cover it.
+    // Signer proposals run as the contract itself and pay it from the contract's balance.
+    let mut refund = info.funds.clone();
+    if info.sender != env.contract.address {
+        let fee = TokenQuerier::new(&deps.querier)
+            .params()?
+            .params
+            .denom_creation_fee;
+
+        for f in fee.iter() {
+            let paid = refund
+                .iter_mut()
+                .find(|c| c.denom == f.denom && c.amount >= f.amount)
+                .ok_or_else(|| ContractError::CreationFeeNotPaid {
+                    fee: pretty_denoms_output(&fee),
+                })?;
+            paid.amount -= f.amount;
+        }
+        refund.retain(|c| !c.amount.is_zero());
+    }
+
+    let (full_denom, create_msg, mint_msgs) =
+        build_new_denom(deps.storage, deps.api, &env.contract.address, denom)?;
+
+    if DENOMS.has(deps.storage, &full_denom) {
+        return Err(ContractError::InvalidDenom {
+            denom: full_denom,
+            message: "Denom is already managed by this contract".to_string(),
+        });
+    }
+    DENOMS.save(deps.storage, &full_denom, &Empty {})?;
+
+    let mut res = Response::new()
+        .add_attribute("method", "create_denom")
+        .add_attribute("denom", full_denom)
+        .add_message(create_msg)
+        .add_messages(mint_msgs);
+
+    if !refund.is_empty() {
+        res = res.add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: refund,
+        });
+    }
+
+    Ok(res)
+}
+
+pub fn execute_set_max_supply(
+    deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    denom: String,
+    max_supply: Option<Uint128>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+    ensure_managed_denom(deps.storage, &denom)?;
+
+    match max_supply {
+        Some(max_supply) => MAX_SUPPLY.save(deps.storage, &denom, &max_supply)?,
+        None => MAX_SUPPLY.remove(deps.storage, &denom),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_max_supply")
+        .add_attribute("denom", denom)
+        .add_attribute(
+            "max_supply",
+            max_supply.map_or_else(|| "none".to_string(), |m| m.to_string()),
+        ))
+}
+
+pub fn execute_transfer_admin(
+    deps: DepsMut<TokenFactoryQuery>,
+    denom: String,
+    new_addr: String,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    deps.api.addr_validate(&new_addr)?;
+
+    // it is possible to transfer admin in without adding to contract config. So devs need a way to reclaim admin without adding it to denoms config
+    DENOMS.remove(deps.storage, &denom);
+
+    let msg = TokenFactoryMsg::ChangeAdmin {
+        denom: denom.to_string(),
+        new_admin_address: new_addr.to_string(),
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_transfer_admin")
+        .add_attribute("new_admin", new_addr)
+        .add_message(msg))
+}
+
+pub fn execute_burn_from(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    burner: Addr,
+    is_burner: bool,
+    from: String,
+    denom: Coin,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    validate_burn_from(deps.as_ref(), &env.contract.address, &from, &denom)?;
+    spend_burn_allowance(
+        deps.storage,
+        &env.block,
+        &env.contract.address,
+        &from,
+        &denom,
+        &burner,
+        is_burner,
+    )?;
+
+    let balance = deps.querier.query_all_balances(from.clone())?;
+
+    let mut found = false;
+    for coin in balance.iter() {
+        if coin.denom == denom.denom {
+            found = true;
+        }
+    }
+
+    if !found {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.denom,
+            message: "Denom not found in balance".to_string(),
+        });
+    }
+
+    record_burn(
+        deps.storage,
+        env.block.height,
+        burner.as_str(),
+        &[denom.clone()],
+    )?;
+
+    // burn from from_address
+    let msg: TokenFactoryMsg = TokenFactoryMsg::BurnTokens {
+        denom: denom.denom.clone(),
+        amount: denom.amount,
+        burn_from_address: from.clone(),
+    };
+
+    let hooks = token_hook_msgs(
+        deps.storage,
+        TokenHookMsg {
+            operation: TokenOperation::BurnFrom,
+            denom: denom.denom.clone(),
+            amount: denom.amount,
+            from: Some(from.clone()),
+            recipient: None,
+            sender: burner.to_string(),
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_burn_from")
+        .add_attribute("denom", denom.denom)
+        .add_message(msg)
+        .add_submessages(hooks))
+}
+
+/// The checks BurnFrom makes before spending an allowance, also made when it is queued.
+fn validate_burn_from(
+    deps: Deps<TokenFactoryQuery>,
+    contract: &Addr,
+    from: &str,
+    denom: &Coin,
+) -> Result<(), ContractError> {
+    deps.api.addr_validate(from)?;
+    ensure_not_contract_funds(contract, from)?;
+    validate_managed_coin(deps.storage, denom)?;
+    ensure_not_paused(deps.storage, &denom.denom, "burn_from", |p| p.burn_from)
+}
+
+pub fn execute_force_transfer(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    operator: Addr,
+    from: String,
+    to: String,
+    denom: Coin,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    validate_force_transfer(deps.as_ref(), &env.contract.address, &from, &to, &denom)?;
+
+    let hooks = token_hook_msgs(
+        deps.storage,
+        TokenHookMsg {
+            operation: TokenOperation::ForceTransfer,
+            denom: denom.denom.clone(),
+            amount: denom.amount,
+            from: Some(from.clone()),
+            recipient: Some(to.clone()),
+            sender: operator.to_string(),
+        },
+    )?;
+
+    FORCED_TRANSFER.save(
+        deps.storage,
+        &ForcedTransfer {
+            denom: denom.denom.clone(),
+            from: from.clone(),
+            to: to.clone(),
+        },
+    )?;
+
+    let msg: TokenFactoryMsg = TokenFactoryMsg::ForceTransfer {
+        denom: denom.denom.clone(),
+        amount: denom.amount,
+        from_address: from,
+        to_address: to,
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_force_transfer")
+        .add_attribute("denom", denom.denom)
+        .add_submessage(SubMsg::reply_on_success(msg, FORCE_TRANSFER_REPLY_ID))
+        .add_submessages(hooks))
+}
+
+/// The checks ForceTransfer makes before moving any tokens, also made when it is queued.
+fn validate_force_transfer(
+    deps: Deps<TokenFactoryQuery>,
+    contract: &Addr,
+    from: &str,
+    to: &str,
+    denom: &Coin,
+) -> Result<(), ContractError> {
+    deps.api.addr_validate(from)?;
+    deps.api.addr_validate(to)?;
+    ensure_not_contract_funds(contract, from)?;
+    validate_managed_coin(deps.storage, denom)?;
+    ensure_not_paused(deps.storage, &denom.denom, "force_transfer", |p| {
+        p.force_transfer
+    })
+}
+
+pub fn execute_transfer_from(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    spender: Addr,
+    owner: String,
+    recipient: String,
+    denom: Coin,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    deps.api.addr_validate(&owner)?;
+    deps.api.addr_validate(&recipient)?;
+    ensure_not_contract_funds(&env.contract.address, &owner)?;
+    validate_managed_coin(deps.storage, &denom)?;
+    ensure_not_paused(deps.storage, &denom.denom, "transfer_from", |p| {
+        p.transfer_from
+    })?;
+    // ForceTransfer skips the checks the before-send hook makes on regular sends
+    ensure_transfer_allowed(
+        deps.as_ref(),
+        &env.contract.address,
+        &denom.denom,
+        &owner,
+        &recipient,
+    )?;
+    spend_allowance(deps.storage, &env.block, &owner, spender.as_str(), &denom)?;
+
+    let hooks = token_hook_msgs(
+        deps.storage,
+        TokenHookMsg {
+            operation: TokenOperation::TransferFrom,
+            denom: denom.denom.clone(),
+            amount: denom.amount,
+            from: Some(owner.clone()),
+            recipient: Some(recipient.clone()),
+            sender: spender.to_string(),
+        },
+    )?;
+
+    let msg: TokenFactoryMsg = TokenFactoryMsg::ForceTransfer {
+        denom: denom.denom.clone(),
+        amount: denom.amount,
+        from_address: owner.clone(),
+        to_address: recipient.clone(),
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_transfer_from")
+        .add_attribute("owner", owner)
+        .add_attribute("recipient", recipient)
+        .add_attribute("spender", spender)
+        .add_attribute("denom", denom.denom)
+        .add_attribute("amount", denom.amount)
+        .add_message(msg)
+        .add_submessages(hooks))
+}
+
+/// Callbacks for every subscriber to the denom, or to every denom. A contract subscribed
+/// both ways is only called once, with the denom subscription's settings.
+fn token_hook_msgs(
+    storage: &dyn Storage,
+    hook: TokenHookMsg,
+) -> StdResult<Vec<SubMsg<TokenFactoryMsg>>> {
+    let mut subscribers: Vec<(String, HookSubscription)> = DENOM_HOOKS
+        .prefix(&hook.denom)
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<_>>()?;
+    for item in GLOBAL_HOOKS.range(storage, None, None, Order::Ascending) {
+        let (contract, subscription) = item?;
+        if !subscribers.iter().any(|(c, _)| c == &contract) {
+            subscribers.push((contract, subscription));
+        }
+    }
+
+    let msg = to_binary(&HookExecuteMsg::TokenHook(hook))?;
+    Ok(subscribers
+        .into_iter()
+        .map(|(contract, subscription)| {
+            let wasm = WasmMsg::Execute {
+                contract_addr: contract,
+                msg: msg.clone(),
+                funds: vec![],
+            };
+            if subscription.tolerate_errors {
+                SubMsg::reply_on_error(wasm, HOOK_REPLY_ID)
+            } else {
+                SubMsg::new(wasm)
+            }
+        })
+        .collect())
+}
+
+fn mint_hook_msgs(
+    storage: &dyn Storage,
+    minter: &Addr,
+    recipient: &str,
+    coins: &[Coin],
+) -> StdResult<Vec<SubMsg<TokenFactoryMsg>>> {
+    let mut hooks = vec![];
+    for coin in coins {
+        hooks.extend(token_hook_msgs(
+            storage,
+            TokenHookMsg {
+                operation: TokenOperation::Mint,
+                denom: coin.denom.clone(),
+                amount: coin.amount,
+                from: None,
+                recipient: Some(recipient.to_string()),
+                sender: minter.to_string(),
+            },
+        )?);
+    }
+    Ok(hooks)
+}
+
+pub fn execute_remove_denom(
+    deps: DepsMut<TokenFactoryQuery>,
+    denoms: Vec<String>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    validate_remove_denom(deps.storage, &denoms)?;
+    for remove in denoms {
+        DENOMS.remove(deps.storage, &remove);
+    }
+
+    Ok(Response::new().add_attribute("method", "remove_denom"))
+}
+
+/// Checks every denom to remove is managed, also when the removal is queued.
+fn validate_remove_denom(storage: &dyn Storage, denoms: &[String]) -> Result<(), ContractError> {
+    if denoms.is_empty() {
+        return Err(ContractError::NoDenomsProvided {});
+    }
+    for denom in denoms.iter() {
+        ensure_managed_denom(storage, denom)?;
+    }
+    Ok(())
+}
+
+/// Returns the audit log action name, the denoms involved and the reason of privileged messages.
+fn audit_details(
+    contract: &Addr,
+    msg: &ExecuteMsg,
+) -> Option<(&'static str, Vec<String>, Option<String>)> {
+    let one = |denom: &str| vec![denom.to_string()];
+    let (action, denoms, reason) = match msg {
+        ExecuteMsg::BurnFrom { denom, reason, .. } => ("burn_from", one(&denom.denom), reason),
+        ExecuteMsg::TransferAdmin { denom, reason, .. } => ("transfer_admin", one(denom), reason),
+        ExecuteMsg::ForceTransfer { denom, reason, .. } => {
+            ("force_transfer", one(&denom.denom), reason)
+        }
+        ExecuteMsg::SetMetadata { denom, reason, .. } => ("set_metadata", one(denom), reason),
+        ExecuteMsg::AddWhitelist { denoms, reason, .. } => {
+            ("add_whitelist", denoms.clone().unwrap_or_default(), reason)
+        }
+        ExecuteMsg::RemoveWhitelist { reason, .. } => ("remove_whitelist", vec![], reason),
+        ExecuteMsg::SetMinterGroup { reason, .. } => ("set_minter_group", vec![], reason),
+        ExecuteMsg::SetMintAllowances {
+            allowances, reason, ..
+        } => (
+            "set_mint_allowances",
+            allowances.iter().map(|c| c.denom.clone()).collect(),
+            reason,
+        ),
+        ExecuteMsg::RemoveMintAllowances { denoms, reason, .. } => {
+            ("remove_mint_allowances", denoms.clone(), reason)
+        }
+        ExecuteMsg::AddDenom { denoms, reason } => ("add_denom", denoms.clone(), reason),
+        ExecuteMsg::RemoveDenom { denoms, reason } => ("remove_denom", denoms.clone(), reason),
+        ExecuteMsg::CreateDenom { denom, reason } => (
+            "create_denom",
+            vec![format!(
+                "factory/{}/{}",
+                contract,
+                denom.symbol.to_lowercase()
+            )],
+            reason,
+        ),
+        ExecuteMsg::SetMaxSupply { denom, reason, .. } => ("set_max_supply", one(denom), reason),
+        ExecuteMsg::SetPaused { denom, reason, .. } => {
+            ("set_paused", denom.iter().cloned().collect(), reason)
+        }
+        ExecuteMsg::Freeze { denom, reason, .. } => ("freeze", one(denom), reason),
+        ExecuteMsg::Unfreeze { denom, reason, .. } => ("unfreeze", one(denom), reason),
+        ExecuteMsg::SetBeforeSendHook { denom, reason, .. } => {
+            ("set_before_send_hook", one(denom), reason)
+        }
+        ExecuteMsg::SetBurnConsentRequired { denom, reason, .. } => {
+            ("set_burn_consent_required", one(denom), reason)
+        }
+        ExecuteMsg::AddHook { denom, reason, .. } => {
+            ("add_hook", denom.iter().cloned().collect(), reason)
+        }
+        ExecuteMsg::RemoveHook { denom, reason, .. } => {
+            ("remove_hook", denom.iter().cloned().collect(), reason)
+        }
+        ExecuteMsg::SetTransferPolicy { denom, reason, .. } => {
+            ("set_transfer_policy", one(denom), reason)
+        }
+        ExecuteMsg::AddTransferAllowlist { denom, reason, .. } => {
+            ("add_transfer_allowlist", one(denom), reason)
+        }
+        ExecuteMsg::RemoveTransferAllowlist { denom, reason, .. } => {
+            ("remove_transfer_allowlist", one(denom), reason)
+        }
+        ExecuteMsg::GrantRole { reason, .. } => ("grant_role", vec![], reason),
+        ExecuteMsg::RevokeRole { reason, .. } => ("revoke_role", vec![], reason),
+        ExecuteMsg::RevokeVesting { reason, .. } => ("revoke_vesting", vec![], reason),
+        ExecuteMsg::ProposeManager { reason, .. } => ("propose_manager", vec![], reason),
+        ExecuteMsg::CancelManagerProposal { reason } => ("cancel_manager_proposal", vec![], reason),
+        ExecuteMsg::RenounceManager { reason } => ("renounce_manager", vec![], reason),
+        ExecuteMsg::SetTimelock { reason, .. } => ("set_timelock", vec![], reason),
+        ExecuteMsg::SetGuardian { reason, .. } => ("set_guardian", vec![], reason),
+        ExecuteMsg::CancelPendingAction { reason, .. } => ("cancel_pending_action", vec![], reason),
+        ExecuteMsg::SetSigners { reason, .. } => ("set_signers", vec![], reason),
+        ExecuteMsg::AcceptManager {} => return Some(("accept_manager", vec![], None)),
+        _ => return None,
+    };
+    Some((action, denoms, reason.clone()))
+}
+
+/// Appends `msg` to the audit log when it is a privileged action.
+/// `pending_action` is set when a queued action runs.
+fn record_audit(
+    storage: &mut dyn Storage,
+    env: &Env,
+    sender: &Addr,
+    msg: &ExecuteMsg,
+    pending_action: Option<u64>,
+) -> StdResult<()> {
+    let (action, denoms, reason) = match audit_details(&env.contract.address, msg) {
+        Some(details) => details,
+        None => return Ok(()),
+    };
+
+    save_audit_entry(
+        storage,
+        &AuditEntry {
+            action: action.to_string(),
+            sender: sender.to_string(),
+            denoms,
+            reason,
+            msg: msg.clone(),
+            pending_action,
+            height: env.block.height,
+            time: env.block.time,
+        },
+    )
+}
+
+fn save_audit_entry(storage: &mut dyn Storage, entry: &AuditEntry) -> StdResult<()> {
+    let id = AUDIT_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    AUDIT_COUNT.save(storage, &id)?;
+
+    AUDIT_BY_ACTION.save(storage, (&entry.action, id), &Empty {})?;
+    for denom in &entry.denoms {
+        AUDIT_BY_DENOM.save(storage, (denom, id), &Empty {})?;
+    }
+    AUDIT_LOG.save(storage, id, entry)
+}
+
+/// Returns the timelocked action `msg` is, if any.
+fn timelocked_action(msg: &ExecuteMsg) -> Option<TimelockedAction> {
+    match msg {
+        ExecuteMsg::ForceTransfer { .. } => Some(TimelockedAction::ForceTransfer),
+        ExecuteMsg::BurnFrom { .. } => Some(TimelockedAction::BurnFrom),
+        ExecuteMsg::TransferAdmin { .. } => Some(TimelockedAction::TransferAdmin),
+        ExecuteMsg::RemoveDenom { .. } => Some(TimelockedAction::RemoveDenom),
+        _ => None,
+    }
+}
+
+/// The role needed to request, and later run, a timelocked action.
+fn timelocked_role(action: TimelockedAction) -> Role {
+    match action {
+        TimelockedAction::ForceTransfer => Role::ClawbackOperator,
+        TimelockedAction::BurnFrom => Role::Burner,
+        TimelockedAction::TransferAdmin | TimelockedAction::RemoveDenom => Role::SuperAdmin,
+    }
+}
+
+fn queue_action(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    action: TimelockedAction,
+    delay: u64,
+    msg: ExecuteMsg,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, timelocked_role(action), &info.sender)?;
+
+    // Fail now rather than once the delay has passed. The checks run again at execution.
+    let contract = &env.contract.address;
+    match &msg {
+        ExecuteMsg::ForceTransfer {
+            from, to, denom, ..
+        } => validate_force_transfer(deps.as_ref(), contract, from, to, denom)?,
+        ExecuteMsg::BurnFrom { from, denom, .. } => {
+            validate_burn_from(deps.as_ref(), contract, from, denom)?
+        }
+        ExecuteMsg::TransferAdmin { new_address, .. } => {
+            deps.api.addr_validate(new_address)?;
+        }
+        ExecuteMsg::RemoveDenom { denoms, .. } => validate_remove_denom(deps.storage, denoms)?,
+        _ => return Err(ContractError::NotTimelockable {}),
+    }
+
+    save_pending_action(
+        deps.storage,
+        &env,
+        &info.sender,
+        action.as_str(),
+        delay,
+        msg,
+    )
+}
+
+/// Saves `msg` to be run with ExecutePendingAction once `delay` seconds have passed.
+fn save_pending_action(
+    storage: &mut dyn Storage,
+    env: &Env,
+    proposer: &Addr,
+    action: &str,
+    delay: u64,
+    msg: ExecuteMsg,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let id = PENDING_ACTION_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    PENDING_ACTION_COUNT.save(storage, &id)?;
+
+    let eta = env.block.time.plus_seconds(delay);
+    PENDING_ACTIONS.save(
+        storage,
+        id,
+        &PendingAction {
+            msg,
+            proposer: proposer.to_string(),
+            eta,
+        },
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "queue_action")
+        .add_attribute("action", action)
+        .add_attribute("id", id.to_string())
+        .add_attribute("eta", eta.seconds().to_string()))
+}
+
+pub fn execute_set_timelock(
+    deps: DepsMut<TokenFactoryQuery>,
+    action: TimelockedAction,
+    delay: Option<u64>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    match delay {
+        Some(delay) => TIMELOCKS.save(deps.storage, action.as_str(), &delay)?,
+        None => TIMELOCKS.remove(deps.storage, action.as_str()),
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_timelock")
+        .add_attribute("action", action.as_str())
+        .add_attribute("delay", delay.unwrap_or_default().to_string()))
+}
+
+/// Runs a queued action. Its proposer must still be allowed to make it, and the rest of its
+/// checks run now.
+pub fn execute_pending_action(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    id: u64,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let pending = PENDING_ACTIONS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::PendingActionNotFound { id })?;
+    if env.block.time < pending.eta {
+        return Err(ContractError::TimelockNotExpired {
+            id,
+            eta: pending.eta,
+        });
+    }
+    PENDING_ACTIONS.remove(deps.storage, id);
+
+    // Roles may have been revoked, or the manager changed, while the action waited.
+    let config = CONFIG.load(deps.storage)?;
+    let proposer = Addr::unchecked(pending.proposer);
+    match timelocked_action(&pending.msg) {
+        Some(action) => has_role(deps.storage, &config, timelocked_role(action), &proposer)?,
+        None => is_contract_manager(config, proposer.clone())?,
+    }
+
+    // Logged again now it takes effect, with the proposer and reason it was requested with.
+    record_audit(deps.storage, &env, &proposer, &pending.msg, Some(id))?;
+
+    let res = match pending.msg {
+        ExecuteMsg::ForceTransfer {
+            from, to, denom, ..
+        } => execute_force_transfer(deps, env, proposer, from, to, denom),
+        ExecuteMsg::BurnFrom { from, denom, .. } => {
+            execute_burn_from(deps, env, proposer, true, from, denom)
+        }
+        ExecuteMsg::TransferAdmin {
+            denom, new_address, ..
+        } => execute_transfer_admin(deps, denom, new_address),
+        ExecuteMsg::RemoveDenom { denoms, .. } => execute_remove_denom(deps, denoms),
+        ExecuteMsg::SetTimelock { action, delay, .. } => execute_set_timelock(deps, action, delay),
+        _ => Err(ContractError::NotTimelockable {}),
+    }?;
+
+    Ok(res.add_attribute("pending_action", id.to_string()))
+}
+
+pub fn execute_set_mint_allowances(
+    deps: DepsMut<TokenFactoryQuery>,
+    info: MessageInfo,
+    address: String,
+    allowances: Vec<Coin>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::MinterAdmin, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?.to_string();
+
+    for coin in allowances.iter() {
+        ensure_managed_denom(deps.storage, &coin.denom)?;
+        MINT_ALLOWANCES.save(deps.storage, (&address, &coin.denom), &coin.amount)?;
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "set_mint_allowances")
+        .add_attribute("address", address)
+        .add_attribute("allowances", pretty_denoms_output(&allowances)))
+}
+
+pub fn execute_mint(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    address: String,
+    denoms: Vec<Coin>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let denoms = prepare_mint(deps.branch(), &env, &info.sender, denoms)?;
+
+    let mint_msgs: Vec<TokenFactoryMsg> = mint_factory_token_messages(&address, &denoms)?;
+    let hooks = mint_hook_msgs(deps.storage, &info.sender, &address, &denoms)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_mint")
+        .add_attribute("to_address", address)
+        .add_attribute("denoms", pretty_denoms_output(&denoms))
+        .add_messages(mint_msgs)
+        .add_submessages(hooks))
+}
+
+pub fn execute_mint_and_call(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    denoms: Vec<Coin>,
+    msg: Binary,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    let denoms = prepare_mint(deps.branch(), &env, &info.sender, denoms)?;
+
+    // Minted to this contract first, so they can be sent along with the call.
+    let core = env.contract.address.to_string();
+    let mint_msgs: Vec<TokenFactoryMsg> = mint_factory_token_messages(&core, &denoms)?;
+    let hooks = mint_hook_msgs(deps.storage, &info.sender, contract.as_str(), &denoms)?;
+
+    let call = WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(&MintReceiverExecuteMsg::MintReceive(MintReceiveMsg {
+            sender: info.sender.to_string(),
+            msg,
+        }))?,
+        funds: denoms.clone(),
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_mint_and_call")
+        .add_attribute("contract", contract)
+        .add_attribute("denoms", pretty_denoms_output(&denoms))
+        .add_messages(mint_msgs)
+        .add_message(call)
+        .add_submessages(hooks))
+}
+
+/// Runs every check for a whitelisted mint of `denoms` and records it.
+/// Returns the denoms with duplicates merged.
+fn prepare_mint(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: &Env,
+    sender: &Addr,
+    denoms: Vec<Coin>,
+) -> Result<Vec<Coin>, ContractError> {
+    let minter = is_whitelisted(deps.as_ref(), sender)?;
+    let denoms = validate_managed_coins(deps.storage, denoms)?;
+
+    authorize_mint(deps.storage, sender.as_str(), &minter, &denoms)?;
+    check_max_supply(deps.as_ref(), &denoms)?;
+    record_mint(deps.storage, env.block.height, sender.as_str(), &denoms)?;
+
+    Ok(denoms)
+}
+
+pub fn execute_mint_batch(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    mints: Vec<MintBatchEntry>,
+    mode: MintBatchMode,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    // The whitelist is only checked once for the whole batch.
+    let minter = is_whitelisted(deps.as_ref(), &info.sender)?;
+    if mints.is_empty() {
+        return Err(ContractError::EmptyBatch {});
+    }
+
+    match mode {
+        MintBatchMode::AllOrNothing => mint_batch_all_or_nothing(deps, env, info, minter, mints),
+        MintBatchMode::BestEffort => mint_batch_best_effort(deps, env, info, minter, mints),
+    }
+}
+
+fn mint_batch_all_or_nothing(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    minter: MinterInfo,
+    mints: Vec<MintBatchEntry>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let mut entries: Vec<(String, Vec<Coin>)> = Vec::with_capacity(mints.len());
+    for mint in mints {
+        deps.api.addr_validate(&mint.address)?;
+        let coins = validate_managed_coins(deps.storage, mint.denom)?;
+        entries.push((mint.address, coins));
+    }
+
+    // Allowances and max supply are checked against the batch total.
+    let total = merge_coins(entries.iter().flat_map(|(_, coins)| coins.iter().cloned()))?;
+    authorize_mint(deps.storage, info.sender.as_str(), &minter, &total)?;
+    check_max_supply(deps.as_ref(), &total)?;
+    record_mint(deps.storage, env.block.height, info.sender.as_str(), &total)?;
+
+    let mut mint_msgs: Vec<TokenFactoryMsg> = vec![];
+    let mut hooks: Vec<SubMsg<TokenFactoryMsg>> = vec![];
+    for (address, coins) in entries.iter() {
+        mint_msgs.extend(mint_factory_token_messages(address, coins)?);
+        hooks.extend(mint_hook_msgs(deps.storage, &info.sender, address, coins)?);
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_mint_batch")
+        .add_attribute("mode", "all_or_nothing")
+        .add_attribute("recipients", entries.len().to_string())
+        .add_attribute("denoms", pretty_denoms_output(&total))
+        .add_messages(mint_msgs)
+        .add_submessages(hooks))
+}
+
+fn mint_batch_best_effort(
+    mut deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    minter: MinterInfo,
+    mints: Vec<MintBatchEntry>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let mut res = Response::new()
+        .add_attribute("method", "execute_mint_batch")
+        .add_attribute("mode", "best_effort");
+
+    // Coins accepted so far, so later entries are checked against the max supply including them.
+    let mut accepted: Vec<Coin> = vec![];
+    let mut pending: Vec<PendingBatchMint> = vec![];
+
+    for (index, mint) in mints.into_iter().enumerate() {
+        let address = mint.address.clone();
+        match try_batch_entry(deps.branch(), &info.sender, &minter, &accepted, mint) {
+            Ok(coins) => {
+                record_mint(deps.storage, env.block.height, info.sender.as_str(), &coins)?;
+
+                for coin in coins.iter() {
+                    let msg = TokenFactoryMsg::MintTokens {
+                        denom: coin.denom.clone(),
+                        amount: coin.amount,
+                        mint_to_address: address.clone(),
+                    };
+                    res = res.add_submessage(SubMsg::reply_always(msg, MINT_BATCH_REPLY_ID));
+                    pending.push(PendingBatchMint {
+                        index: index as u64,
+                        minter: info.sender.to_string(),
+                        address: address.clone(),
+                        coin: coin.clone(),
+                    });
+                }
+                accepted.extend(coins);
+            }
+            Err(err) => {
+                res = res.add_attribute("failed", format!("{}: {}", index, err));
+            }
+        }
+    }
+
+    // A batch can start while another waits on replies, when a hook subscriber mints in one.
+    // Submessages run depth first, so this batch's replies all come before the waiting batch's
+    // next one and its mints go in front of the queue.
+    if !pending.is_empty() {
+        let waiting = PENDING_BATCH_MINTS
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+        pending.extend(waiting);
+        PENDING_BATCH_MINTS.save(deps.storage, &pending)?;
+    }
+
+    Ok(res.add_attribute("denoms", pretty_denoms_output(&merge_coins(accepted)?)))
+}
+
+/// Runs every check for one best-effort batch entry and spends the minter's allowance for it.
+fn try_batch_entry(
+    deps: DepsMut<TokenFactoryQuery>,
+    sender: &Addr,
+    minter: &MinterInfo,
+    accepted: &[Coin],
+    mint: MintBatchEntry,
+) -> Result<Vec<Coin>, ContractError> {
+    deps.api.addr_validate(&mint.address)?;
+    let coins = validate_managed_coins(deps.storage, mint.denom)?;
+
+    let with_entry: Vec<Coin> = accepted.iter().chain(coins.iter()).cloned().collect();
+    check_max_supply(deps.as_ref(), &with_entry)?;
+    authorize_mint(deps.storage, sender.as_str(), minter, &coins)?;
+
+    Ok(coins)
+}
+
+pub fn execute_mint_vesting(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    denom: Coin,
+    schedule: VestingSchedule,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    deps.api.addr_validate(&recipient)?;
+    validate_managed_coin(deps.storage, &denom)?;
+    validate_vesting_schedule(&schedule)?;
+
+    // Whitelisted minters go through their usual checks, otherwise only a super admin can mint.
+    let coins = vec![denom.clone()];
+    match load_minter(deps.as_ref(), info.sender.as_str())? {
+        Some(minter) => authorize_mint(deps.storage, info.sender.as_str(), &minter, &coins)?,
+        None => {
+            let config = CONFIG.load(deps.storage)?;
+            has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+            ensure_not_paused(deps.storage, &denom.denom, "mint", |p| p.mint)?;
+
+            // Minting without being whitelisted is a manager action
+            save_audit_entry(
+                deps.storage,
+                &AuditEntry {
+                    action: "mint_vesting".to_string(),
+                    sender: info.sender.to_string(),
+                    denoms: vec![denom.denom.clone()],
+                    reason: None,
+                    msg: ExecuteMsg::MintVesting {
+                        recipient: recipient.clone(),
+                        denom: denom.clone(),
+                        schedule: schedule.clone(),
+                    },
+                    pending_action: None,
+                    height: env.block.height,
+                    time: env.block.time,
+                },
+            )?;
+        }
+    }
+    check_max_supply(deps.as_ref(), &coins)?;
+    record_mint(deps.storage, env.block.height, info.sender.as_str(), &coins)?;
+
+    let id = save_vesting(deps.storage, &recipient, &denom, schedule)?;
+
+    // The tokens are minted to the contract, which holds them until they are claimed.
+    let msg = mint_tokens_msg(
+        env.contract.address.to_string(),
+        denom.denom.clone(),
+        denom.amount,
+    );
+    let hooks = mint_hook_msgs(
+        deps.storage,
+        &info.sender,
+        env.contract.address.as_str(),
+        &coins,
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_mint_vesting")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipient", recipient)
+        .add_attribute("denom", denom.to_string())
+        .add_message(msg)
+        .add_submessages(hooks))
+}
+
+pub fn execute_claim_vested(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    ids: Option<Vec<u64>>,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let ids = match ids {
+        Some(ids) => ids,
+        None => RECIPIENT_VESTINGS
+            .prefix(info.sender.as_str())
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<u64>>>()?,
+    };
+
+    let mut claimed: Vec<Coin> = vec![];
+    for id in ids {
+        let mut vesting = VESTINGS
+            .may_load(deps.storage, id)?
+            .ok_or(ContractError::VestingNotFound { id })?;
+        if vesting.recipient != info.sender.as_str() {
+            return Err(ContractError::Unauthorized {});
+        }
+
+        let claimable = vested_amount(&vesting, &env.block)
+            .checked_sub(vesting.claimed)
+            .map_err(StdError::from)?;
+        if claimable.is_zero() {
+            continue;
+        }
+
+        vesting.claimed += claimable;
+        if vesting.claimed == vesting.total {
+            VESTINGS.remove(deps.storage, id);
+            RECIPIENT_VESTINGS.remove(deps.storage, (vesting.recipient.as_str(), id));
+        } else {
+            VESTINGS.save(deps.storage, id, &vesting)?;
+        }
+
+        claimed.push(Coin {
+            denom: vesting.denom,
+            amount: claimable,
+        });
+    }
+
+    if claimed.is_empty() {
+        return Err(ContractError::NothingToClaim {});
+    }
+    let claimed = merge_coins(claimed)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_claim_vested")
+        .add_attribute("recipient", info.sender.to_string())
+        .add_attribute("claimed", pretty_denoms_output(&claimed))
+        .add_message(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: claimed,
+        }))
+}
+
+pub fn execute_revoke_vesting(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    has_role(deps.storage, &config, Role::SuperAdmin, &info.sender)?;
+
+    let mut vesting = VESTINGS
+        .may_load(deps.storage, id)?
+        .ok_or(ContractError::VestingNotFound { id })?;
+    if !vesting.schedule.revocable || vesting.revoked {
+        return Err(ContractError::VestingNotRevocable { id });
+    }
+
+    let vested = vested_amount(&vesting, &env.block);
+    let unvested = vesting.total.checked_sub(vested).map_err(StdError::from)?;
+
+    vesting.total = vested;
+    vesting.revoked = true;
+    if vesting.claimed == vesting.total {
+        VESTINGS.remove(deps.storage, id);
+        RECIPIENT_VESTINGS.remove(deps.storage, (vesting.recipient.as_str(), id));
+    } else {
+        VESTINGS.save(deps.storage, id, &vesting)?;
+    }
+
+    let mut res = Response::new()
+        .add_attribute("method", "execute_revoke_vesting")
+        .add_attribute("id", id.to_string())
+        .add_attribute("recipient", &vesting.recipient)
+        .add_attribute("burned", unvested.to_string());
+
+    // The unvested tokens are held by the contract, so they are burned from it.
+    if !unvested.is_zero() {
+        let coins = vec![Coin {
+            denom: vesting.denom.clone(),
+            amount: unvested,
+        }];
+        record_burn(deps.storage, env.block.height, info.sender.as_str(), &coins)?;
+
+        let hooks = token_hook_msgs(
+            deps.storage,
+            TokenHookMsg {
+                operation: TokenOperation::Burn,
+                denom: vesting.denom.clone(),
+                amount: unvested,
+                from: Some(env.contract.address.to_string()),
+                recipient: None,
+                sender: info.sender.to_string(),
+            },
+        )?;
+
+        res = res
+            .add_message(TokenFactoryMsg::BurnTokens {
+                denom: vesting.denom,
+                amount: unvested,
+                burn_from_address: env.contract.address.to_string(),
+            })
+            .add_submessages(hooks);
+    }
+
+    Ok(res)
+}
+
+pub fn execute_burn(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    // Anyone can burn funds since they have to send them in.
+    if info.funds.is_empty() {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    let (factory_denoms, send_back): (Vec<Coin>, Vec<Coin>) = info
+        .funds
+        .iter()
+        .cloned()
+        .partition(|coin| DENOMS.has(deps.storage, &coin.denom));
+
+    for coin in factory_denoms.iter() {
+        ensure_not_paused(deps.storage, &coin.denom, "burn", |p| p.burn)?;
+    }
+    record_burn(
+        deps.storage,
+        env.block.height,
+        info.sender.as_str(),
+        &factory_denoms,
+    )?;
+
+    let burn_msgs: Vec<TokenFactoryMsg> = factory_denoms
+        .iter()
+        .map(|coin| TokenFactoryMsg::BurnTokens {
+            denom: coin.denom.clone(),
+            amount: coin.amount,
+            burn_from_address: env.contract.address.to_string(),
+        })
+        .collect();
+
+    let mut hooks: Vec<SubMsg<TokenFactoryMsg>> = vec![];
+    for coin in factory_denoms.iter() {
+        hooks.extend(token_hook_msgs(
+            deps.storage,
+            TokenHookMsg {
+                operation: TokenOperation::Burn,
+                denom: coin.denom.clone(),
+                amount: coin.amount,
+                from: Some(info.sender.to_string()),
+                recipient: None,
+                sender: info.sender.to_string(),
+            },
+        )?);
+    }
+
+    let bank_return_msg = BankMsg::Send {
+        to_address: info.sender.to_string(),
+        amount: send_back,
+    };
+
+    Ok(Response::new()
+        .add_attribute("method", "execute_burn")
+        .add_message(bank_return_msg)
+        .add_messages(burn_msgs)
+        .add_submessages(hooks))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    msg: SudoMsg,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    match msg {
+        SudoMsg::BlockBeforeSend { from, to, amount } => {
+            // Clawbacks made by this contract go through, frozen or not.
+            let forced = ForcedTransfer {
+                denom: amount.denom.clone(),
+                from: from.clone(),
+                to: to.clone(),
+            };
+            if FORCED_TRANSFER.may_load(deps.storage)? == Some(forced) {
+                return Ok(Response::new());
+            }
+
+            ensure_transfer_allowed(
+                deps.as_ref(),
+                &env.contract.address,
+                &amount.denom,
+                &from,
+                &to,
+            )?;
+            Ok(Response::new())
+        }
+        SudoMsg::TrackBeforeSend { .. } => Ok(Response::new()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    msg: Reply,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    match msg.id {
+        MINT_BATCH_REPLY_ID => reply_mint_batch(deps, env, msg.result),
+        // Only sent with reply_on_error, for subscribers which tolerate errors
+        HOOK_REPLY_ID => {
+            let error = match msg.result {
+                SubMsgResult::Err(err) => err,
+                SubMsgResult::Ok(_) => String::new(),
+            };
+            Ok(Response::new()
+                .add_attribute("method", "reply_hook")
+                .add_attribute("error", error))
+        }
+        // Only sent with reply_on_success, a failed ForceTransfer reverts the flag with the rest
+        FORCE_TRANSFER_REPLY_ID => {
+            FORCED_TRANSFER.remove(deps.storage);
+            Ok(Response::new().add_attribute("method", "reply_force_transfer"))
+        }
+        id => Err(ContractError::UnknownReplyId { id }),
+    }
+}
+
+fn reply_mint_batch(
+    deps: DepsMut<TokenFactoryQuery>,
+    env: Env,
+    result: SubMsgResult,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    // Replies arrive in the order the submessages were sent, nested batches first.
+    let mut pending = PENDING_BATCH_MINTS.load(deps.storage)?;
+    let mint = pending.remove(0);
+    if pending.is_empty() {
+        PENDING_BATCH_MINTS.remove(deps.storage);
+    } else {
+        PENDING_BATCH_MINTS.save(deps.storage, &pending)?;
+    }
+
+    let res = Response::new()
+        .add_attribute("method", "reply_mint_batch")
+        .add_attribute("index", mint.index.to_string())
+        .add_attribute("address", &mint.address)
+        .add_attribute("denom", mint.coin.to_string());
+
+    match result {
+        SubMsgResult::Ok(_) => {
+            let minter = Addr::unchecked(mint.minter);
+            let hooks = mint_hook_msgs(deps.storage, &minter, &mint.address, &[mint.coin])?;
+            Ok(res.add_attribute("result", "ok").add_submessages(hooks))
+        }
+        SubMsgResult::Err(err) => {
+            let coins = vec![mint.coin];
+            refund_mint_allowances(deps.storage, &mint.minter, &coins)?;
+            unrecord_mint(deps.storage, env.block.height, &mint.minter, &coins)?;
+
+            Ok(res
+                .add_attribute("result", "failed")
+                .add_attribute("error", err))
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(
+    deps: DepsMut<TokenFactoryQuery>,
+    _env: Env,
+    _msg: MigrateMsg,
+) -> Result<Response<TokenFactoryMsg>, ContractError> {
+    let stored = get_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            contract: stored.contract,
+            expected: CONTRACT_NAME.to_string(),
+        });
+    }
+
+    // Refuse downgrades, the state may already use a newer layout.
+    let previous: Version = stored.version.parse()?;
+    let new: Version = CONTRACT_VERSION.parse()?;
+    if previous > new {
+        return Err(ContractError::CannotMigrateVersion {
+            previous: previous.to_string(),
+            new: new.to_string(),
+        });
+    }
+
+    migrate_state(deps.storage, &previous)?;
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("previous_version", previous.to_string())
+        .add_attribute("new_version", new.to_string()))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps<TokenFactoryQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::GetConfig {} => {
+            let config = CONFIG.load(deps.storage)?;
+            to_binary(&config)
+        }
+        QueryMsg::GetBalance { address, denom } => {
+            let v = BankQuery::Balance { address, denom };
+            let res: BalanceResponse = deps.querier.query(&v.into())?;
+            to_binary(&res.amount)
+        }
+
+        // Since RPC's do not like to return factory/ denoms. We allow that through this query
+        QueryMsg::GetAllBalances { address } => {
+            let v = BankQuery::AllBalances { address };
+
+            let res: AllBalanceResponse = deps.querier.query(&v.into())?;
+
+            to_binary(&res.amount)
+        }
+
+        QueryMsg::GetSupply { denom } => to_binary(&deps.querier.query_supply(denom)?),
+        QueryMsg::GetDenomAdmin { denom } => {
+            to_binary(&TokenQuerier::new(&deps.querier).admin(denom)?.admin)
+        }
+        QueryMsg::GetDenomMetadata { denom } => {
+            to_binary(&TokenQuerier::new(&deps.querier).metadata(denom)?.metadata)
+        }
+        QueryMsg::GetDenomInfo { denom } => to_binary(&query_denom_info(deps, denom)?),
+
+        QueryMsg::GetDenomTotals { denom, height } => {
+            let (minted, burned) = match height {
+                Some(height) => (
+                    MINTED.may_load_at_height(deps.storage, &denom, height)?,
+                    BURNED.may_load_at_height(deps.storage, &denom, height)?,
+                ),
+                None => (
+                    MINTED.may_load(deps.storage, &denom)?,
+                    BURNED.may_load(deps.storage, &denom)?,
+                ),
+            };
+
+            to_binary(&TotalsResponse {
+                minted: minted.unwrap_or_default(),
+                burned: burned.unwrap_or_default(),
+            })
+        }
+        QueryMsg::GetAddressTotals {
+            address,
+            denom,
+            height,
+        } => {
+            let key = (address.as_str(), denom.as_str());
+            let (minted, burned) = match height {
+                Some(height) => (
+                    MINTED_BY.may_load_at_height(deps.storage, key, height)?,
+                    BURNED_BY.may_load_at_height(deps.storage, key, height)?,
+                ),
+                None => (
+                    MINTED_BY.may_load(deps.storage, key)?,
+                    BURNED_BY.may_load(deps.storage, key)?,
+                ),
+            };
+
+            to_binary(&TotalsResponse {
+                minted: minted.unwrap_or_default(),
+                burned: burned.unwrap_or_default(),
+            })
+        }
+
+        QueryMsg::GetRoleMembers { role } => {
+            let members = ROLES
+                .prefix(role.as_str())
+                .keys(deps.storage, None, None, Order::Ascending)
+                .collect::<StdResult<Vec<String>>>()?;
+            to_binary(&members)
+        }
+        QueryMsg::GetRoles { address } => {
+            let roles: Vec<Role> = Role::all()
+                .iter()
+                .copied()
+                .filter(|r| ROLES.has(deps.storage, (r.as_str(), address.as_str())))
+                .collect();
+            to_binary(&roles)
+        }
+
+        QueryMsg::GetPauseState { denom } => {
+            let global = GLOBAL_PAUSE.may_load(deps.storage)?.unwrap_or_default();
+            let denom = match denom {
+                Some(denom) => Some(
+                    DENOM_PAUSE
+                        .may_load(deps.storage, &denom)?
+                        .unwrap_or_default(),
+                ),
+                None => None,
+            };
+
+            to_binary(&PauseStateResponse { global, denom })
+        }
+        QueryMsg::GetPendingManager {} => to_binary(&PENDING_MANAGER.may_load(deps.storage)?),
+        QueryMsg::GetMaxSupply { denom } => to_binary(&MAX_SUPPLY.may_load(deps.storage, &denom)?),
+
+        QueryMsg::GetMinter { address } => to_binary(&load_minter(deps, &address)?),
+        QueryMsg::GetMinterGroup {} => to_binary(&MINTER_GROUP.may_load(deps.storage)?),
+        QueryMsg::GetTransferPolicy { denom } => to_binary(
+            &TRANSFER_POLICY
+                .may_load(deps.storage, &denom)?
+                .unwrap_or(TransferPolicy::Open),
+        ),
+        QueryMsg::IsTransferAllowlisted { denom, address } => {
+            to_binary(&TRANSFER_ALLOWLIST.has(deps.storage, (&denom, &address)))
+        }
+        QueryMsg::ListTransferAllowlist {
+            denom,
+            start_after,
+            limit,
+        } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let allowlist = TRANSFER_ALLOWLIST
+                .prefix(&denom)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .collect::<StdResult<Vec<String>>>()?;
+
+            to_binary(&allowlist)
+        }
+        QueryMsg::GetAllowance {
+            owner,
+            spender,
+            denom,
+        } => {
+            let allowance = ALLOWANCES.may_load(deps.storage, (&owner, &denom, &spender))?;
+            to_binary(&AllowanceResponse {
+                amount: allowance.as_ref().map(|a| a.amount).unwrap_or_default(),
+                expires: allowance.and_then(|a| a.expires),
+            })
+        }
+        QueryMsg::ListAllowances {
+            owner,
+            start_after,
+            limit,
+        } => {
+            let start = start_after
+                .as_ref()
+                .map(|(denom, spender)| Bound::exclusive((denom.as_str(), spender.as_str())));
+
+            let allowances = ALLOWANCES
+                .sub_prefix(&owner)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|item| {
+                    item.map(|((denom, spender), allowance)| AllowanceInfo {
+                        denom,
+                        spender,
+                        amount: allowance.amount,
+                        expires: allowance.expires,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&allowances)
+        }
+        QueryMsg::IsBurnConsentRequired { denom } => {
+            to_binary(&BURN_CONSENT_REQUIRED.has(deps.storage, &denom))
+        }
+        QueryMsg::GetBurnAllowance {
+            holder,
+            denom,
+            burner,
+        } => {
+            let burner = burner.unwrap_or_else(|| env.contract.address.to_string());
+            to_binary(&BURN_ALLOWANCES.may_load(deps.storage, (&holder, &denom, &burner))?)
+        }
+        QueryMsg::ListBurnAllowances {
+            holder,
+            start_after,
+            limit,
+        } => {
+            let start = start_after
+                .as_ref()
+                .map(|(denom, burner)| Bound::exclusive((denom.as_str(), burner.as_str())));
+
+            let allowances = BURN_ALLOWANCES
+                .sub_prefix(&holder)
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|item| {
+                    item.map(|((denom, burner), allowance)| BurnAllowanceResponse {
+                        denom,
+                        burner,
+                        amount: allowance.amount,
+                        expires: allowance.expires,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&allowances)
+        }
+        QueryMsg::ListHooks {
+            denom,
+            start_after,
+            limit,
+        } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let subscriptions = match denom.as_ref() {
+                Some(denom) => DENOM_HOOKS
+                    .prefix(denom)
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(page_limit(limit))
+                    .collect::<StdResult<Vec<_>>>()?,
+                None => GLOBAL_HOOKS
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .take(page_limit(limit))
+                    .collect::<StdResult<Vec<_>>>()?,
+            };
+
+            let hooks: Vec<HookResponse> = subscriptions
+                .into_iter()
+                .map(|(contract, subscription)| HookResponse {
+                    contract,
+                    tolerate_errors: subscription.tolerate_errors,
+                })
+                .collect();
+
+            to_binary(&hooks)
+        }
+        QueryMsg::IsFrozen { denom, address } => {
+            to_binary(&FROZEN.has(deps.storage, (&denom, &address)))
+        }
+        QueryMsg::ListFrozen {
+            denom,
+            start_after,
+            limit,
+        } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let frozen = FROZEN
+                .prefix(&denom)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .collect::<StdResult<Vec<String>>>()?;
+
+            to_binary(&frozen)
+        }
+        QueryMsg::ListMinters { start_after, limit } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let minters = MINTERS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|item| {
+                    item.map(|(address, minter)| MinterResponse {
+                        address,
+                        denoms: minter.denoms,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&minters)
+        }
+        QueryMsg::ReconcileDenoms { start_after, limit } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+            let querier = TokenQuerier::new(&deps.querier);
+
+            let denoms = DENOMS
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .collect::<StdResult<Vec<String>>>()?;
+
+            let mut uncontrolled: Vec<UncontrolledDenomResponse> = vec![];
+            for denom in denoms {
+                let admin = querier.admin(denom.clone())?.admin;
+                if admin != env.contract.address.as_str() {
+                    uncontrolled.push(UncontrolledDenomResponse { denom, admin });
+                }
+            }
+
+            to_binary(&uncontrolled)
+        }
+        QueryMsg::ListDenoms { start_after, limit } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let denoms = DENOMS
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .collect::<StdResult<Vec<String>>>()?;
+
+            to_binary(&denoms)
+        }
+        QueryMsg::GetMintAllowances { address } => {
+            to_binary(&get_mint_allowances(deps.storage, &address)?)
+        }
+        QueryMsg::GetAllMintAllowances { start_after, limit } => {
+            let start = start_after.as_deref().map(Bound::exclusive);
+
+            let res = MINTERS
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|address| -> StdResult<_> {
+                    let address = address?;
+                    let allowances = get_mint_allowances(deps.storage, &address)?;
+                    Ok(MintAllowancesResponse {
+                        address,
+                        allowances,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&res)
+        }
+        QueryMsg::GetTimelocks {} => {
+            let timelocks = TimelockedAction::all()
+                .iter()
+                .map(|action| -> StdResult<_> {
+                    Ok(TIMELOCKS
+                        .may_load(deps.storage, action.as_str())?
+                        .map(|delay| TimelockResponse {
+                            action: *action,
+                            delay,
+                        }))
+                })
+                .filter_map(|res| res.transpose())
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&timelocks)
+        }
+        QueryMsg::GetGuardian {} => to_binary(&GUARDIAN.may_load(deps.storage)?),
+        QueryMsg::ListPendingActions { start_after, limit } => {
+            let start = start_after.map(Bound::exclusive);
+
+            let actions = PENDING_ACTIONS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|item| {
+                    item.map(|(id, action)| PendingActionResponse {
+                        id,
+                        msg: action.msg,
+                        proposer: action.proposer,
+                        eta: action.eta,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&actions)
+        }
+        QueryMsg::GetSigners {} => to_binary(&SIGNERS.may_load(deps.storage)?),
+        QueryMsg::GetProposal { id } => {
+            let proposal = PROPOSALS
+                .may_load(deps.storage, id)?
+                .map(|proposal| proposal_response(id, proposal, &env.block));
+            to_binary(&proposal)
+        }
+        QueryMsg::ListProposals { start_after, limit } => {
+            let start = start_after.map(Bound::exclusive);
+
+            let proposals = PROPOSALS
+                .range(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|item| item.map(|(id, proposal)| proposal_response(id, proposal, &env.block)))
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&proposals)
+        }
+        QueryMsg::GetVesting { id } => {
+            let vesting = VESTINGS
+                .may_load(deps.storage, id)?
+                .map(|vesting| vesting_response(id, vesting, &env.block));
+            to_binary(&vesting)
+        }
+        QueryMsg::AuditLog {
+            action,
+            denom,
+            start_after,
+            limit,
+        } => {
+            let start = start_after.map(Bound::exclusive);
+
+            let ids: Box<dyn Iterator<Item = StdResult<u64>> + '_> = match (&action, &denom) {
+                (Some(action), _) => Box::new(AUDIT_BY_ACTION.prefix(action).keys(
+                    deps.storage,
+                    start,
+                    None,
+                    Order::Ascending,
+                )),
+                (None, Some(denom)) => Box::new(AUDIT_BY_DENOM.prefix(denom).keys(
+                    deps.storage,
+                    start,
+                    None,
+                    Order::Ascending,
+                )),
+                (None, None) => {
+                    Box::new(AUDIT_LOG.keys(deps.storage, start, None, Order::Ascending))
+                }
+            };
+
+            let entries = ids
+                .map(|id| -> StdResult<_> {
+                    let id = id?;
+                    Ok((id, AUDIT_LOG.load(deps.storage, id)?))
+                })
+                // Filtering by both goes through the action index
+                .filter(|item| match (item, &denom) {
+                    (Ok((_, entry)), Some(denom)) => entry.denoms.contains(denom),
+                    _ => true,
+                })
+                .take(page_limit(limit))
+                .map(|item| {
+                    item.map(|(id, entry)| AuditEntryResponse {
+                        id,
+                        action: entry.action,
+                        sender: entry.sender,
+                        denoms: entry.denoms,
+                        reason: entry.reason,
+                        msg: entry.msg,
+                        pending_action: entry.pending_action,
+                        height: entry.height,
+                        time: entry.time,
+                    })
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&entries)
+        }
+        QueryMsg::ListVestings {
+            recipient,
+            start_after,
+            limit,
+        } => {
+            let start = start_after.map(Bound::exclusive);
+
+            let vestings = RECIPIENT_VESTINGS
+                .prefix(&recipient)
+                .keys(deps.storage, start, None, Order::Ascending)
+                .take(page_limit(limit))
+                .map(|id| -> StdResult<_> {
+                    let id = id?;
+                    let vesting = VESTINGS.load(deps.storage, id)?;
+                    Ok(vesting_response(id, vesting, &env.block))
+                })
+                .collect::<StdResult<Vec<_>>>()?;
+
+            to_binary(&vestings)
+        }
+    }
+}
+
+fn proposal_response(id: u64, proposal: Proposal, block: &BlockInfo) -> ProposalResponse {
+    ProposalResponse {
+        id,
+        expired: block.time >= proposal.expires,
+        msg: proposal.msg,
+        proposer: proposal.proposer,
+        approvals: proposal.approvals,
+        expires: proposal.expires,
+    }
+}
+
+fn vesting_response(id: u64, vesting: Vesting, block: &BlockInfo) -> VestingResponse {
+    let vested = vested_amount(&vesting, block);
+    VestingResponse {
+        id,
+        recipient: vesting.recipient,
+        denom: vesting.denom,
+        total: vesting.total,
+        claimed: vesting.claimed,
+        vested,
+        revoked: vesting.revoked,
+        schedule: vesting.schedule,
+    }
+}
+
+fn query_denom_info(deps: Deps<TokenFactoryQuery>, denom: String) -> StdResult<DenomInfoResponse> {
+    let querier = TokenQuerier::new(&deps.querier);
+    let admin = querier.admin(denom.clone())?.admin;
+    let metadata = querier.metadata(denom.clone())?.metadata;
+    let supply = deps.querier.query_supply(denom.clone())?.amount;
+
+    let paused = PauseStateResponse {
+        global: GLOBAL_PAUSE.may_load(deps.storage)?.unwrap_or_default(),
+        denom: Some(
+            DENOM_PAUSE
+                .may_load(deps.storage, &denom)?
+                .unwrap_or_default(),
+        ),
+    };
+
+    let transfer_policy = TRANSFER_POLICY
+        .may_load(deps.storage, &denom)?
+        .unwrap_or(TransferPolicy::Open);
+
+    Ok(DenomInfoResponse {
+        managed: DENOMS.has(deps.storage, &denom),
+        max_supply: MAX_SUPPLY.may_load(deps.storage, &denom)?,
+        denom,
+        supply,
+        admin,
+        metadata,
+        minter_group: MINTER_GROUP.may_load(deps.storage)?.map(|g| g.address),
+        paused,
+        transfer_policy,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
+    use cosmwasm_std::{
+        attr, Api, CanonicalAddr, OwnedDeps, RecoverPubkeyError, SubMsgResponse, VerificationError,
+    };
+    use std::marker::PhantomData;
+
+    const MANAGER: &str = "juno1manager";
+    const MINTER: &str = "juno1minter";
+    const DENOM: &str = "factory/juno1contract/test";
+    const MODULE: &str = "tokenfactory_module";
+
+    /// MockApi can not turn the 20 byte module account back into an address, so it is
+    /// humanized as MODULE here.
+    #[derive(Default)]
+    struct TestApi(MockApi);
+
+    impl Api for TestApi {
+        fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+            self.0.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+            self.0.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+            if canonical.len() == 20 {
+                return Ok(Addr::unchecked(MODULE));
+            }
+            self.0.addr_humanize(canonical)
+        }
+
+        fn secp256k1_verify(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            recovery_param: u8,
+        ) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.0
+                .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(
+            &self,
+            messages: &[&[u8]],
+            signatures: &[&[u8]],
+            public_keys: &[&[u8]],
+        ) -> Result<bool, VerificationError> {
+            self.0
+                .ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message: &str) {
+            self.0.debug(message)
+        }
+    }
+
+    type TestDeps =
+        OwnedDeps<MockStorage, TestApi, MockQuerier<TokenFactoryQuery>, TokenFactoryQuery>;
+
+    fn mock_deps() -> TestDeps {
+        OwnedDeps {
+            storage: MockStorage::default(),
+            api: TestApi::default(),
+            querier: MockQuerier::new(&[]),
+            custom_query_type: PhantomData,
+        }
+    }
+
+    /// MANAGER manages the contract, which manages DENOM.
+    fn setup() -> TestDeps {
+        let mut deps = mock_deps();
+        CONFIG
+            .save(
+                &mut deps.storage,
+                &Config {
+                    manager: Some(MANAGER.to_string()),
+                },
+            )
+            .unwrap();
+        DENOMS.save(&mut deps.storage, DENOM, &Empty {}).unwrap();
+        deps
+    }
+
+    fn exec(
+        deps: &mut TestDeps,
+        env: &Env,
+        sender: &str,
+        msg: ExecuteMsg,
+    ) -> Result<Response<TokenFactoryMsg>, ContractError> {
+        execute(deps.as_mut(), env.clone(), mock_info(sender, &[]), msg)
+    }
+
+    fn pause_msg(paused: PausedOperations) -> ExecuteMsg {
+        ExecuteMsg::SetPaused {
+            denom: Some(DENOM.to_string()),
+            paused,
+            reason: None,
+        }
+    }
+
+    fn timelock_msg(action: TimelockedAction, delay: Option<u64>) -> ExecuteMsg {
+        ExecuteMsg::SetTimelock {
+            action,
+            delay,
+            reason: None,
+        }
+    }
+
+    fn force_transfer_msg(denom: Coin) -> ExecuteMsg {
+        ExecuteMsg::ForceTransfer {
+            from: "juno1holder".to_string(),
+            to: "juno1other".to_string(),
+            denom,
+            reason: None,
+        }
+    }
+
+    /// Hands the manager over to three signers, two of whom have to approve proposals.
+    fn set_signers(deps: &mut TestDeps, env: &Env) {
+        let msg = ExecuteMsg::SetSigners {
+            signers: vec![
+                "juno1alice".to_string(),
+                "juno1bob".to_string(),
+                "juno1carol".to_string(),
+            ],
+            threshold: 2,
+            proposal_duration: 100,
+            reason: None,
+        };
+        exec(deps, env, MANAGER, msg).unwrap();
+    }
+
+    fn freeze_msg(address: &str) -> ExecuteMsg {
+        ExecuteMsg::Freeze {
+            denom: DENOM.to_string(),
+            addresses: vec![address.to_string()],
+            reason: None,
+        }
+    }
+
+    fn before_send(
+        deps: &mut TestDeps,
+        from: &str,
+        to: &str,
+    ) -> Result<Response<TokenFactoryMsg>, ContractError> {
+        let msg = SudoMsg::BlockBeforeSend {
+            from: from.to_string(),
+            to: to.to_string(),
+            amount: Coin::new(10, DENOM),
+        };
+        sudo(deps.as_mut(), mock_env(), msg)
+    }
+
+    fn policy_msg(policy: TransferPolicy) -> ExecuteMsg {
+        ExecuteMsg::SetTransferPolicy {
+            denom: DENOM.to_string(),
+            policy,
+            reason: None,
+        }
+    }
+
+    fn burn_from_msg(from: &str, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::BurnFrom {
+            from: from.to_string(),
+            denom: Coin::new(amount, DENOM),
+            reason: None,
+        }
+    }
+
+    fn burn_allowance_msg(burner: Option<&str>, expires: Option<Expiration>) -> ExecuteMsg {
+        ExecuteMsg::SetBurnAllowance {
+            denom: DENOM.to_string(),
+            burner: burner.map(|b| b.to_string()),
+            amount: Uint128::new(50),
+            expires,
+        }
+    }
+
+    fn burn_consent_msg(required: bool) -> ExecuteMsg {
+        ExecuteMsg::SetBurnConsentRequired {
+            denom: DENOM.to_string(),
+            required,
+            reason: None,
+        }
+    }
+
+    fn increase_allowance_msg(amount: u128, expires: Option<Expiration>) -> ExecuteMsg {
+        ExecuteMsg::IncreaseAllowance {
+            spender: "juno1spender".to_string(),
+            denom: Coin::new(amount, DENOM),
+            expires,
+        }
+    }
+
+    fn transfer_from_msg(owner: &str, amount: u128) -> ExecuteMsg {
+        ExecuteMsg::TransferFrom {
+            owner: owner.to_string(),
+            recipient: "juno1recipient".to_string(),
+            denom: Coin::new(amount, DENOM),
+        }
+    }
+
+    fn grant_msg(role: Role, address: &str) -> ExecuteMsg {
+        ExecuteMsg::GrantRole {
+            role,
+            addresses: vec![address.to_string()],
+            reason: None,
+        }
+    }
+
+    fn pending_mint(index: u64, address: &str) -> PendingBatchMint {
+        PendingBatchMint {
+            index,
+            minter: MINTER.to_string(),
+            address: address.to_string(),
+            coin: Coin::new(40, DENOM),
+        }
+    }
+
+    fn batch_reply(result: SubMsgResult) -> Reply {
+        Reply {
+            id: MINT_BATCH_REPLY_ID,
+            result,
+        }
+    }
+
+    #[test]
+    fn best_effort_batch_replies_undo_failed_mints() {
+        let mut deps = mock_deps();
+        let env = mock_env();
+
+        // Both entries spent their allowance and were recorded when the batch was sent.
+        let coin = Coin::new(40, DENOM);
+        MINT_ALLOWANCES
+            .save(&mut deps.storage, (MINTER, DENOM), &Uint128::new(20))
+            .unwrap();
+        record_mint(
+            &mut deps.storage,
+            env.block.height,
+            MINTER,
+            &[coin.clone(), coin],
+        )
+        .unwrap();
+        PENDING_BATCH_MINTS
+            .save(
+                &mut deps.storage,
+                &vec![
+                    pending_mint(0, "juno1first"),
+                    pending_mint(1, "juno1second"),
+                ],
+            )
+            .unwrap();
+
+        let res = reply(
+            deps.as_mut(),
+            env.clone(),
+            batch_reply(SubMsgResult::Err("mint failed".to_string())),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("index", "0")));
+        assert!(res.attributes.contains(&attr("result", "failed")));
+
+        assert_eq!(
+            MINT_ALLOWANCES
+                .load(&deps.storage, (MINTER, DENOM))
+                .unwrap(),
+            Uint128::new(60)
+        );
+        assert_eq!(MINTED.load(&deps.storage, DENOM).unwrap(), Uint128::new(40));
+        assert_eq!(
+            MINTED_BY.load(&deps.storage, (MINTER, DENOM)).unwrap(),
+            Uint128::new(40)
+        );
+        assert_eq!(
+            PENDING_BATCH_MINTS.load(&deps.storage).unwrap(),
+            vec![pending_mint(1, "juno1second")]
+        );
+
+        let res = reply(
+            deps.as_mut(),
+            env,
+            batch_reply(SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            })),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("index", "1")));
+        assert!(res.attributes.contains(&attr("result", "ok")));
+
+        // A successful mint keeps what it spent and recorded.
+        assert_eq!(
+            MINT_ALLOWANCES
+                .load(&deps.storage, (MINTER, DENOM))
+                .unwrap(),
+            Uint128::new(60)
+        );
+        assert_eq!(MINTED.load(&deps.storage, DENOM).unwrap(), Uint128::new(40));
+        assert!(PENDING_BATCH_MINTS
+            .may_load(&deps.storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn failed_mints_without_an_allowance_only_undo_totals() {
+        let mut deps = mock_deps();
+        let env = mock_env();
+
+        record_mint(
+            &mut deps.storage,
+            env.block.height,
+            MINTER,
+            &[Coin::new(40, DENOM)],
+        )
+        .unwrap();
+        PENDING_BATCH_MINTS
+            .save(&mut deps.storage, &vec![pending_mint(0, "juno1first")])
+            .unwrap();
+
+        reply(
+            deps.as_mut(),
+            env,
+            batch_reply(SubMsgResult::Err("mint failed".to_string())),
+        )
+        .unwrap();
+
+        assert!(MINT_ALLOWANCES
+            .may_load(&deps.storage, (MINTER, DENOM))
+            .unwrap()
+            .is_none());
+        assert_eq!(MINTED.load(&deps.storage, DENOM).unwrap(), Uint128::zero());
+        assert!(PENDING_BATCH_MINTS
+            .may_load(&deps.storage)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn batch_replies_need_a_waiting_mint() {
+        let mut deps = mock_deps();
+        let err = reply(
+            deps.as_mut(),
+            mock_env(),
+            batch_reply(SubMsgResult::Err("mint failed".to_string())),
+        );
+        assert!(err.is_err());
+    }
+
+    #[test]
+    fn roles_only_cover_their_own_actions() {
+        let mut deps = setup();
+        let env = mock_env();
+        let pause = PausedOperations {
+            mint: true,
+            ..Default::default()
+        };
+
+        let err = exec(&mut deps, &env, "juno1pauser", pause_msg(pause.clone())).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::Pauser, "juno1pauser"),
+        )
+        .unwrap();
+        exec(&mut deps, &env, "juno1pauser", pause_msg(pause)).unwrap();
+
+        let freeze = ExecuteMsg::Freeze {
+            denom: DENOM.to_string(),
+            addresses: vec!["juno1holder".to_string()],
+            reason: None,
+        };
+        let err = exec(&mut deps, &env, "juno1pauser", freeze).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Only super admins hand out roles.
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1pauser",
+            grant_msg(Role::Pauser, "juno1other"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn only_the_manager_grants_super_admin() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::SuperAdmin, "juno1admin"),
+        )
+        .unwrap();
+        exec(
+            &mut deps,
+            &env,
+            "juno1admin",
+            grant_msg(Role::Freezer, "juno1freezer"),
+        )
+        .unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1admin",
+            grant_msg(Role::SuperAdmin, "juno1other"),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn renouncing_the_manager_clears_roles() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::Pauser, "juno1pauser"),
+        )
+        .unwrap();
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::SuperAdmin, "juno1admin"),
+        )
+        .unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1admin",
+            ExecuteMsg::RenounceManager { reason: None },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            ExecuteMsg::RenounceManager { reason: None },
+        )
+        .unwrap();
+        assert!(ROLES
+            .keys(&deps.storage, None, None, Order::Ascending)
+            .next()
+            .is_none());
+
+        let pause = PausedOperations {
+            mint: true,
+            ..Default::default()
+        };
+        let err = exec(&mut deps, &env, "juno1pauser", pause_msg(pause)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn timelocked_actions_wait_for_their_delay() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let delay = timelock_msg(TimelockedAction::ForceTransfer, Some(100));
+        exec(&mut deps, &env, MANAGER, delay).unwrap();
+
+        let res = exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("method", "queue_action")));
+        assert!(res.messages.is_empty());
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1anyone",
+            ExecuteMsg::ExecutePendingAction { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TimelockNotExpired {
+                id: 1,
+                eta: env.block.time.plus_seconds(100),
+            }
+        );
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let res = exec(
+            &mut deps,
+            &env,
+            "juno1anyone",
+            ExecuteMsg::ExecutePendingAction { id: 1 },
+        )
+        .unwrap();
+        assert!(res.attributes.contains(&attr("pending_action", "1")));
+        assert_eq!(res.messages.len(), 1);
+        assert!(!PENDING_ACTIONS.has(&deps.storage, 1));
+    }
+
+    #[test]
+    fn timelocked_actions_are_checked_when_queued() {
+        let mut deps = setup();
+        let env = mock_env();
+        let delay = timelock_msg(TimelockedAction::ForceTransfer, Some(100));
+        exec(&mut deps, &env, MANAGER, delay).unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1stranger",
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let err = exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(0, DENOM)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ZeroAmount {
+                denom: DENOM.to_string()
+            }
+        );
+
+        let err = exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(10, "ujuno")),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::DenomNotManaged {
+                denom: "ujuno".to_string()
+            }
+        );
+
+        let pause = PausedOperations {
+            force_transfer: true,
+            ..Default::default()
+        };
+        exec(&mut deps, &env, MANAGER, pause_msg(pause)).unwrap();
+        let err = exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                operation: "force_transfer".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn revoked_proposers_can_not_run_their_actions() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let delay = timelock_msg(TimelockedAction::ForceTransfer, Some(100));
+        exec(&mut deps, &env, MANAGER, delay).unwrap();
+        let operator = grant_msg(Role::ClawbackOperator, "juno1operator");
+        exec(&mut deps, &env, MANAGER, operator).unwrap();
+
+        exec(
+            &mut deps,
+            &env,
+            "juno1operator",
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap();
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            ExecuteMsg::RevokeRole {
+                role: Role::ClawbackOperator,
+                addresses: vec!["juno1operator".to_string()],
+                reason: None,
+            },
+        )
+        .unwrap();
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1anyone",
+            ExecuteMsg::ExecutePendingAction { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+    }
+
+    #[test]
+    fn only_the_manager_or_guardian_cancel_pending_actions() {
+        let mut deps = setup();
+        let env = mock_env();
+        let delay = timelock_msg(TimelockedAction::ForceTransfer, Some(100));
+        exec(&mut deps, &env, MANAGER, delay).unwrap();
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap();
+
+        let cancel = ExecuteMsg::CancelPendingAction {
+            id: 1,
+            reason: None,
+        };
+        let err = exec(&mut deps, &env, "juno1guardian", cancel.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let guardian = ExecuteMsg::SetGuardian {
+            address: Some("juno1guardian".to_string()),
+            reason: None,
+        };
+        exec(&mut deps, &env, MANAGER, guardian).unwrap();
+        exec(&mut deps, &env, "juno1guardian", cancel).unwrap();
+        assert!(!PENDING_ACTIONS.has(&deps.storage, 1));
+    }
+
+    #[test]
+    fn lowering_a_timelock_waits_out_the_current_one() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        let delay = timelock_msg(TimelockedAction::ForceTransfer, Some(100));
+        exec(&mut deps, &env, MANAGER, delay).unwrap();
+
+        let lower = timelock_msg(TimelockedAction::ForceTransfer, None);
+        let res = exec(&mut deps, &env, MANAGER, lower).unwrap();
+        assert!(res.attributes.contains(&attr("method", "queue_action")));
+        assert_eq!(
+            TIMELOCKS.load(&deps.storage, "force_transfer").unwrap(),
+            100
+        );
+
+        env.block.time = env.block.time.plus_seconds(100);
+        exec(
+            &mut deps,
+            &env,
+            "juno1anyone",
+            ExecuteMsg::ExecutePendingAction { id: 1 },
+        )
+        .unwrap();
+        assert!(!TIMELOCKS.has(&deps.storage, "force_transfer"));
+    }
+
+    #[test]
+    fn proposals_run_once_enough_signers_approve() {
+        let mut deps = setup();
+        let env = mock_env();
+        set_signers(&mut deps, &env);
+        assert_eq!(
+            CONFIG.load(&deps.storage).unwrap().manager,
+            Some(env.contract.address.to_string())
+        );
+
+        let pause = PausedOperations {
+            mint: true,
+            ..Default::default()
+        };
+        let propose = ExecuteMsg::Propose {
+            msg: Box::new(pause_msg(pause)),
+        };
+        let err = exec(&mut deps, &env, "juno1stranger", propose.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = exec(&mut deps, &env, "juno1alice", propose).unwrap();
+        assert!(res.messages.is_empty());
+
+        let approve = ExecuteMsg::ApproveProposal { id: 1 };
+        let err = exec(&mut deps, &env, "juno1alice", approve.clone()).unwrap_err();
+        assert_eq!(err, ContractError::AlreadyApproved { id: 1 });
+        let err = exec(&mut deps, &env, "juno1stranger", approve.clone()).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        let res = exec(&mut deps, &env, "juno1bob", approve).unwrap();
+        assert!(res.attributes.contains(&attr("executed", "true")));
+        assert_eq!(res.messages.len(), 1);
+        assert!(!PROPOSALS.has(&deps.storage, 1));
+    }
+
+    #[test]
+    fn expired_proposals_can_not_be_approved() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        set_signers(&mut deps, &env);
+
+        let propose = ExecuteMsg::Propose {
+            msg: Box::new(ExecuteMsg::RenounceManager { reason: None }),
+        };
+        exec(&mut deps, &env, "juno1alice", propose).unwrap();
+
+        env.block.time = env.block.time.plus_seconds(100);
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1bob",
+            ExecuteMsg::ApproveProposal { id: 1 },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ProposalExpired { id: 1 });
+    }
+
+    #[test]
+    fn the_threshold_must_be_reachable() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        for threshold in [0, 3] {
+            let msg = ExecuteMsg::SetSigners {
+                // duplicates only count once
+                signers: vec!["juno1alice".to_string(), "juno1alice".to_string()],
+                threshold,
+                proposal_duration: 100,
+                reason: None,
+            };
+            let err = exec(&mut deps, &env, MANAGER, msg).unwrap_err();
+            assert_eq!(err, ContractError::InvalidThreshold {});
+        }
+    }
+
+    #[test]
+    fn roles_are_suspended_under_signers() {
+        let mut deps = setup();
+        let env = mock_env();
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::Pauser, "juno1pauser"),
+        )
+        .unwrap();
+        set_signers(&mut deps, &env);
+
+        let pause = PausedOperations {
+            mint: true,
+            ..Default::default()
+        };
+        let err = exec(&mut deps, &env, "juno1pauser", pause_msg(pause.clone())).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        let err = exec(&mut deps, &env, MANAGER, pause_msg(pause.clone())).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // Approved proposals run as the contract itself.
+        let contract = env.contract.address.to_string();
+        exec(&mut deps, &env, &contract, pause_msg(pause)).unwrap();
+    }
+
+    #[test]
+    fn frozen_accounts_can_not_send_or_receive() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        let err = exec(&mut deps, &env, "juno1stranger", freeze_msg("juno1holder")).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        exec(&mut deps, &env, MANAGER, freeze_msg("juno1holder")).unwrap();
+
+        let frozen = ContractError::Frozen {
+            address: "juno1holder".to_string(),
+            denom: DENOM.to_string(),
+        };
+        assert_eq!(
+            before_send(&mut deps, "juno1holder", "juno1other").unwrap_err(),
+            frozen
+        );
+        assert_eq!(
+            before_send(&mut deps, "juno1other", "juno1holder").unwrap_err(),
+            frozen
+        );
+        before_send(&mut deps, "juno1other", "juno1third").unwrap();
+
+        let unfreeze = ExecuteMsg::Unfreeze {
+            denom: DENOM.to_string(),
+            addresses: vec!["juno1holder".to_string()],
+            reason: None,
+        };
+        exec(&mut deps, &env, MANAGER, unfreeze).unwrap();
+        before_send(&mut deps, "juno1holder", "juno1other").unwrap();
+    }
+
+    #[test]
+    fn frozen_accounts_can_still_be_burned_from() {
+        let mut deps = setup();
+        let env = mock_env();
+        exec(&mut deps, &env, MANAGER, freeze_msg("juno1holder")).unwrap();
+
+        before_send(&mut deps, "juno1holder", MODULE).unwrap();
+    }
+
+    #[test]
+    fn clawbacks_pass_the_freeze() {
+        let mut deps = setup();
+        let env = mock_env();
+        exec(&mut deps, &env, MANAGER, freeze_msg("juno1holder")).unwrap();
+
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            force_transfer_msg(Coin::new(10, DENOM)),
+        )
+        .unwrap();
+        before_send(&mut deps, "juno1holder", "juno1other").unwrap();
+
+        // The flag is cleared once the clawback went through.
+        let done = Reply {
+            id: FORCE_TRANSFER_REPLY_ID,
+            result: SubMsgResult::Ok(SubMsgResponse {
+                events: vec![],
+                data: None,
+            }),
+        };
+        reply(deps.as_mut(), env, done).unwrap();
+        before_send(&mut deps, "juno1holder", "juno1other").unwrap_err();
+    }
+
+    #[test]
+    fn soulbound_denoms_are_only_minted_and_burned() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1stranger",
+            policy_msg(TransferPolicy::Soulbound),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            policy_msg(TransferPolicy::Soulbound),
+        )
+        .unwrap();
+
+        let err = before_send(&mut deps, "juno1holder", "juno1other").unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::TransferNotAllowed {
+                denom: DENOM.to_string(),
+                from: "juno1holder".to_string(),
+                to: "juno1other".to_string(),
+                policy: "soulbound".to_string(),
+            }
+        );
+
+        before_send(&mut deps, MODULE, "juno1holder").unwrap();
+        before_send(&mut deps, "juno1holder", MODULE).unwrap();
+        // vesting claims are sent by the contract
+        let contract = env.contract.address.to_string();
+        before_send(&mut deps, &contract, "juno1holder").unwrap();
+    }
+
+    #[test]
+    fn allowlist_only_needs_both_sides_listed() {
+        let mut deps = setup();
+        let env = mock_env();
+        let policy = policy_msg(TransferPolicy::AllowlistOnly);
+        exec(&mut deps, &env, MANAGER, policy).unwrap();
+
+        let allow = |address: &str| ExecuteMsg::AddTransferAllowlist {
+            denom: DENOM.to_string(),
+            addresses: vec![address.to_string()],
+            reason: None,
+        };
+        let err = exec(&mut deps, &env, "juno1stranger", allow("juno1alice")).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        exec(&mut deps, &env, MANAGER, allow("juno1alice")).unwrap();
+        before_send(&mut deps, "juno1alice", "juno1bob").unwrap_err();
+        before_send(&mut deps, MODULE, "juno1bob").unwrap();
+
+        exec(&mut deps, &env, MANAGER, allow("juno1bob")).unwrap();
+        before_send(&mut deps, "juno1alice", "juno1bob").unwrap();
+    }
+
+    #[test]
+    fn paused_transfers_stop_every_send() {
+        let mut deps = setup();
+        let env = mock_env();
+        let policy = policy_msg(TransferPolicy::TransfersPaused);
+        exec(&mut deps, &env, MANAGER, policy).unwrap();
+
+        before_send(&mut deps, "juno1alice", "juno1bob").unwrap_err();
+
+        exec(&mut deps, &env, MANAGER, policy_msg(TransferPolicy::Open)).unwrap();
+        before_send(&mut deps, "juno1alice", "juno1bob").unwrap();
+    }
+
+    #[test]
+    fn burners_need_consent_once_it_is_required() {
+        let mut deps = setup();
+        let env = mock_env();
+        deps.querier
+            .update_balance("juno1holder", vec![Coin::new(100, DENOM)]);
+        exec(
+            &mut deps,
+            &env,
+            MANAGER,
+            grant_msg(Role::Burner, "juno1burner"),
+        )
+        .unwrap();
+        exec(&mut deps, &env, MANAGER, burn_consent_msg(true)).unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1burner",
+            burn_from_msg("juno1holder", 30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NoBurnAllowance {
+                holder: "juno1holder".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+
+        // Without a burner, any Burner can spend it through the contract.
+        exec(
+            &mut deps,
+            &env,
+            "juno1holder",
+            burn_allowance_msg(None, None),
+        )
+        .unwrap();
+        exec(
+            &mut deps,
+            &env,
+            "juno1burner",
+            burn_from_msg("juno1holder", 30),
+        )
+        .unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1burner",
+            burn_from_msg("juno1holder", 30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BurnAllowanceExceeded {
+                holder: "juno1holder".to_string(),
+                denom: DENOM.to_string(),
+                requested: Uint128::new(30),
+                remaining: Uint128::new(20),
+            }
+        );
+    }
+
+    #[test]
+    fn holders_can_let_anyone_burn_until_it_expires() {
+        let mut deps = setup();
+        let mut env = mock_env();
+        deps.querier
+            .update_balance("juno1holder", vec![Coin::new(100, DENOM)]);
+
+        let expired = Expiration::AtHeight(env.block.height);
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1holder",
+            burn_allowance_msg(Some("juno1friend"), Some(expired)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiration {});
+
+        let expires = Expiration::AtHeight(env.block.height + 10);
+        exec(
+            &mut deps,
+            &env,
+            "juno1holder",
+            burn_allowance_msg(Some("juno1friend"), Some(expires)),
+        )
+        .unwrap();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1stranger",
+            burn_from_msg("juno1holder", 20),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        exec(
+            &mut deps,
+            &env,
+            "juno1friend",
+            burn_from_msg("juno1holder", 20),
+        )
+        .unwrap();
+
+        env.block.height += 10;
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1friend",
+            burn_from_msg("juno1holder", 20),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BurnAllowanceExpired {
+                holder: "juno1holder".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn burn_consent_can_not_be_turned_off() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        let err = exec(&mut deps, &env, "juno1stranger", burn_consent_msg(true)).unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        exec(&mut deps, &env, MANAGER, burn_consent_msg(true)).unwrap();
+        let err = exec(&mut deps, &env, MANAGER, burn_consent_msg(false)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::BurnConsentLocked {
+                denom: DENOM.to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn burn_from_is_refused_when_paused_or_from_the_contract() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        let contract = env.contract.address.to_string();
+        let err = exec(&mut deps, &env, MANAGER, burn_from_msg(&contract, 10)).unwrap_err();
+        assert_eq!(err, ContractError::ContractFunds {});
+
+        let pause = PausedOperations {
+            burn_from: true,
+            ..Default::default()
+        };
+        exec(&mut deps, &env, MANAGER, pause_msg(pause)).unwrap();
+        let err = exec(&mut deps, &env, MANAGER, burn_from_msg("juno1holder", 10)).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                operation: "burn_from".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn transfer_from_spends_the_allowance() {
+        let mut deps = setup();
+        let env = mock_env();
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NoAllowance {
+                owner: "juno1owner".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+
+        exec(
+            &mut deps,
+            &env,
+            "juno1owner",
+            increase_allowance_msg(50, None),
+        )
+        .unwrap();
+        let res = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 30),
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 1);
+
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AllowanceExceeded {
+                owner: "juno1owner".to_string(),
+                denom: DENOM.to_string(),
+                requested: Uint128::new(30),
+                remaining: Uint128::new(20),
+            }
+        );
+    }
+
+    #[test]
+    fn expired_allowances_can_not_be_spent() {
+        let mut deps = setup();
+        let mut env = mock_env();
+
+        let expired = Expiration::AtHeight(env.block.height);
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1owner",
+            increase_allowance_msg(50, Some(expired)),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::InvalidExpiration {});
+
+        let expires = Expiration::AtHeight(env.block.height + 10);
+        exec(
+            &mut deps,
+            &env,
+            "juno1owner",
+            increase_allowance_msg(50, Some(expires)),
+        )
+        .unwrap();
+
+        env.block.height += 10;
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::AllowanceExpired {
+                owner: "juno1owner".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn transfer_from_is_refused_when_paused_or_frozen() {
+        let mut deps = setup();
+        let env = mock_env();
+        exec(
+            &mut deps,
+            &env,
+            "juno1owner",
+            increase_allowance_msg(50, None),
+        )
+        .unwrap();
+
+        exec(&mut deps, &env, MANAGER, freeze_msg("juno1owner")).unwrap();
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 10),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Frozen {
+                address: "juno1owner".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+
+        let pause = PausedOperations {
+            transfer_from: true,
+            ..Default::default()
+        };
+        exec(&mut deps, &env, MANAGER, pause_msg(pause)).unwrap();
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg("juno1owner", 10),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Paused {
+                operation: "transfer_from".to_string(),
+                denom: DENOM.to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn the_contract_funds_can_not_be_approved_or_moved() {
+        let mut deps = setup();
+        let env = mock_env();
+        let contract = env.contract.address.to_string();
+
+        // Under signers, an approved proposal runs as the contract and could approve its
+        // own balance.
+        set_signers(&mut deps, &env);
+        let err = exec(&mut deps, &env, &contract, increase_allowance_msg(50, None)).unwrap_err();
+        assert_eq!(err, ContractError::ContractFunds {});
+
+        ALLOWANCES
+            .save(
+                &mut deps.storage,
+                (&contract, DENOM, "juno1spender"),
+                &Allowance {
+                    amount: Uint128::new(50),
+                    expires: None,
+                },
+            )
+            .unwrap();
+        let err = exec(
+            &mut deps,
+            &env,
+            "juno1spender",
+            transfer_from_msg(&contract, 10),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::ContractFunds {});
+    }
 }
@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, Timestamp, Uint128};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -9,12 +9,159 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Semver parsing error: {0}")]
+    SemVer(String),
+
+    #[error("Cannot migrate from contract {contract:?}, expected {expected:?}")]
+    InvalidContractName { contract: String, expected: String },
+
+    #[error("Cannot migrate from version {previous} to older version {new}")]
+    CannotMigrateVersion { previous: String, new: String },
+
     #[error("Invalid denom: {denom:?} {message:?}")]
     InvalidDenom { denom: String, message: String },
 
     #[error("You did not specify any denoms.")]
     NoDenomsProvided {},
 
+    #[error("This contract is not the admin of {denom:?}, its admin is {admin:?}")]
+    NotDenomAdmin { denom: String, admin: String },
+
+    #[error("Denom {denom:?} is not managed by this contract")]
+    DenomNotManaged { denom: String },
+
+    #[error("Amount of {denom:?} must be greater than zero")]
+    ZeroAmount { denom: String },
+
     #[error("Invalid funds")]
     InvalidFunds {},
+
+    #[error("Tokens held by this contract can not be burned or moved from it")]
+    ContractFunds {},
+
+    #[error("The denom creation fee of {fee:?} was not sent")]
+    CreationFeeNotPaid { fee: String },
+
+    #[error("{operation} is paused for {denom:?}")]
+    Paused { operation: String, denom: String },
+
+    #[error("{address:?} is frozen for {denom:?}")]
+    Frozen { address: String, denom: String },
+
+    #[error("Transfer of {denom:?} from {from:?} to {to:?} is not allowed by the {policy} policy")]
+    TransferNotAllowed {
+        denom: String,
+        from: String,
+        to: String,
+        policy: String,
+    },
+
+    #[error("There is no pending manager")]
+    NoPendingManager {},
+
+    #[error("The manager proposal has expired")]
+    ManagerProposalExpired {},
+
+    #[error("Max supply of {max_supply} for {denom:?} exceeded: supply would be {supply}")]
+    MaxSupplyExceeded {
+        denom: String,
+        max_supply: Uint128,
+        supply: Uint128,
+    },
+
+    #[error("{minter:?} is not allowed to mint {denom:?}")]
+    DenomNotAllowedForMinter { minter: String, denom: String },
+
+    #[error("Mint allowance exceeded for {minter:?} on {denom:?}: {requested} requested, {remaining} remaining")]
+    MintAllowanceExceeded {
+        minter: String,
+        denom: String,
+        requested: Uint128,
+        remaining: Uint128,
+    },
+
+    #[error("{holder:?} has not allowed burning {denom:?}")]
+    NoBurnAllowance { holder: String, denom: String },
+
+    #[error("Burn consent is required for {denom:?} and can not be turned off")]
+    BurnConsentLocked { denom: String },
+
+    #[error("Burn allowance of {holder:?} for {denom:?} has expired")]
+    BurnAllowanceExpired { holder: String, denom: String },
+
+    #[error("Burn allowance of {holder:?} for {denom:?} exceeded: {requested} requested, {remaining} remaining")]
+    BurnAllowanceExceeded {
+        holder: String,
+        denom: String,
+        requested: Uint128,
+        remaining: Uint128,
+    },
+
+    #[error("Cannot set an allowance for your own account")]
+    CannotSetOwnAccount {},
+
+    #[error("Allowance expiration is already in the past")]
+    InvalidExpiration {},
+
+    #[error("{owner:?} has no allowance for {denom:?}")]
+    NoAllowance { owner: String, denom: String },
+
+    #[error("Allowance of {owner:?} for {denom:?} has expired")]
+    AllowanceExpired { owner: String, denom: String },
+
+    #[error("Allowance of {owner:?} for {denom:?} exceeded: {requested} requested, {remaining} remaining")]
+    AllowanceExceeded {
+        owner: String,
+        denom: String,
+        requested: Uint128,
+        remaining: Uint128,
+    },
+
+    #[error("Mint batch has no entries")]
+    EmptyBatch {},
+
+    #[error("Unknown reply id {id}")]
+    UnknownReplyId { id: u64 },
+
+    #[error("Invalid vesting schedule: {message}")]
+    InvalidVestingSchedule { message: String },
+
+    #[error("Vesting {id} not found")]
+    VestingNotFound { id: u64 },
+
+    #[error("Vesting {id} can not be revoked")]
+    VestingNotRevocable { id: u64 },
+
+    #[error("Nothing to claim")]
+    NothingToClaim {},
+
+    #[error("Pending action {id} not found")]
+    PendingActionNotFound { id: u64 },
+
+    #[error("This message can not be timelocked")]
+    NotTimelockable {},
+
+    #[error("Pending action {id} can not be executed before {eta}")]
+    TimelockNotExpired { id: u64, eta: Timestamp },
+
+    #[error("Threshold must be between 1 and the number of signers")]
+    InvalidThreshold {},
+
+    #[error("The manager is not held by signers")]
+    SignersNotEnabled {},
+
+    #[error("Proposal {id} not found")]
+    ProposalNotFound { id: u64 },
+
+    #[error("Proposal {id} has expired")]
+    ProposalExpired { id: u64 },
+
+    #[error("Proposal {id} is already approved by this signer")]
+    AlreadyApproved { id: u64 },
+}
+
+impl From<semver::Error> for ContractError {
+    fn from(err: semver::Error) -> Self {
+        Self::SemVer(err.to_string())
+    }
 }
@@ -1,26 +1,662 @@
-use cosmwasm_std::{Addr, Coin, Uint128};
-use token_bindings::{DenomUnit, Metadata, TokenFactoryMsg};
+use std::collections::BTreeMap;
 
-use crate::{msg::NewDenom, state::Config, ContractError};
+use cosmwasm_std::{
+    Addr, Api, Binary, BlockInfo, CanonicalAddr, Coin, CosmosMsg, Deps, Empty, Order, StdError,
+    StdResult, Storage, Uint128,
+};
+use cw_storage_plus::SnapshotMap;
+use juno_tokenfactory_types::msg::{
+    PausedOperations, Role, TransferPolicy, VestingRelease, VestingSchedule, VestingUnit,
+};
+use sha2::{Digest, Sha256};
+use token_bindings::{DenomUnit, Metadata, TokenFactoryMsg, TokenFactoryQuery, TokenQuerier};
+
+use crate::{
+    msg::{Cw4MemberResponse, Cw4QueryMsg, NewDenom},
+    state::{
+        Allowance, BurnAllowance, Config, MinterInfo, Vesting, ALLOWANCES, BURNED, BURNED_BY,
+        BURN_ALLOWANCES, BURN_CONSENT_REQUIRED, DENOMS, DENOM_PAUSE, FROZEN, GLOBAL_PAUSE,
+        MAX_SUPPLY, MINTED, MINTED_BY, MINTERS, MINTER_GROUP, MINT_ALLOWANCES, RECIPIENT_VESTINGS,
+        ROLES, SIGNERS, TRANSFER_ALLOWLIST, TRANSFER_POLICY, VESTINGS, VESTING_COUNT,
+    },
+    ContractError,
+};
 
 pub use juno_tokenfactory_types::msg::ExecuteMsg::Mint;
 
-pub fn is_whitelisted(state: Config, sender: Addr) -> Result<(), ContractError> {
-    if !state.allowed_mint_addresses.contains(&sender.to_string()) {
-        return Err(ContractError::Unauthorized {});
+// Settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
+pub fn page_limit(limit: Option<u32>) -> usize {
+    limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize
+}
+
+pub fn is_whitelisted(
+    deps: Deps<TokenFactoryQuery>,
+    sender: &Addr,
+) -> Result<MinterInfo, ContractError> {
+    load_minter(deps, sender.as_str())?.ok_or(ContractError::Unauthorized {})
+}
+
+/// Whitelisted addresses keep their own denom scope. Otherwise members of the
+/// minter group with enough weight can mint every denom.
+pub fn load_minter(deps: Deps<TokenFactoryQuery>, address: &str) -> StdResult<Option<MinterInfo>> {
+    if let Some(minter) = MINTERS.may_load(deps.storage, address)? {
+        return Ok(Some(minter));
+    }
+
+    let group = match MINTER_GROUP.may_load(deps.storage)? {
+        Some(group) => group,
+        None => return Ok(None),
+    };
+
+    let member: Cw4MemberResponse = deps.querier.query_wasm_smart(
+        group.address,
+        &Cw4QueryMsg::Member {
+            addr: address.to_string(),
+            at_height: None,
+        },
+    )?;
+
+    match member.weight {
+        Some(weight) if weight >= group.min_weight.unwrap_or_default() => {
+            Ok(Some(MinterInfo { denoms: None }))
+        }
+        _ => Ok(None),
+    }
+}
+
+pub fn ensure_managed_denom(storage: &dyn Storage, denom: &str) -> Result<(), ContractError> {
+    if !DENOMS.has(storage, denom) {
+        return Err(ContractError::DenomNotManaged {
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Errors unless the contract is the tokenfactory admin of `denom`, and so can mint and burn it.
+pub fn ensure_denom_admin(
+    deps: Deps<TokenFactoryQuery>,
+    contract: &Addr,
+    denom: &str,
+) -> Result<(), ContractError> {
+    if !denom.starts_with("factory/") {
+        return Err(ContractError::InvalidDenom {
+            denom: denom.to_string(),
+            message: "Denom must start with 'factory/'".to_string(),
+        });
+    }
+
+    let admin = TokenQuerier::new(&deps.querier)
+        .admin(denom.to_string())?
+        .admin;
+    if admin != contract.as_str() {
+        return Err(ContractError::NotDenomAdmin {
+            denom: denom.to_string(),
+            admin,
+        });
+    }
+    Ok(())
+}
+
+pub fn validate_managed_coin(storage: &dyn Storage, coin: &Coin) -> Result<(), ContractError> {
+    ensure_managed_denom(storage, &coin.denom)?;
+    if coin.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {
+            denom: coin.denom.clone(),
+        });
     }
     Ok(())
 }
 
+/// Checks every coin is a non-zero amount of a managed denom.
+/// Coins of the same denom are merged into one, keeping the order they first appear in.
+pub fn validate_managed_coins(
+    storage: &dyn Storage,
+    coins: Vec<Coin>,
+) -> Result<Vec<Coin>, ContractError> {
+    if coins.is_empty() {
+        return Err(ContractError::NoDenomsProvided {});
+    }
+
+    for coin in coins.iter() {
+        validate_managed_coin(storage, coin)?;
+    }
+    Ok(merge_coins(coins)?)
+}
+
+/// Sums coins of the same denom, keeping the order they first appear in.
+pub fn merge_coins(coins: impl IntoIterator<Item = Coin>) -> StdResult<Vec<Coin>> {
+    let mut merged: Vec<Coin> = vec![];
+    for coin in coins {
+        match merged.iter_mut().find(|c| c.denom == coin.denom) {
+            Some(existing) => existing.amount = existing.amount.checked_add(coin.amount)?,
+            None => merged.push(coin),
+        }
+    }
+    Ok(merged)
+}
+
 pub fn is_contract_manager(config: Config, sender: Addr) -> Result<(), ContractError> {
-    if !config.manager.eq(&sender.to_string()) {
+    if config.manager.as_deref() != Some(sender.as_str()) {
         return Err(ContractError::Unauthorized {});
     }
     Ok(())
 }
 
+/// Errors if `address` is this contract, which holds the tokens of open vestings for their
+/// recipients. Those can not be burned, moved or approved away from it.
+pub fn ensure_not_contract_funds(contract: &Addr, address: &str) -> Result<(), ContractError> {
+    if address == contract.as_str() {
+        return Err(ContractError::ContractFunds {});
+    }
+    Ok(())
+}
+
+/// The manager and super-admins hold every role.
+/// Roles are suspended while signers hold the manager, so nothing gets around their threshold.
+pub fn has_role(
+    storage: &dyn Storage,
+    config: &Config,
+    role: Role,
+    sender: &Addr,
+) -> Result<(), ContractError> {
+    if config.manager.as_deref() == Some(sender.as_str()) {
+        return Ok(());
+    }
+    if SIGNERS.exists(storage) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for r in [role, Role::SuperAdmin] {
+        if ROLES.has(storage, (r.as_str(), sender.as_str())) {
+            return Ok(());
+        }
+    }
+    Err(ContractError::Unauthorized {})
+}
+
+/// Errors if `operation` is paused globally or for this denom.
+/// `is_paused` picks the operation's flag out of the stored pause state.
+pub fn ensure_not_paused(
+    storage: &dyn Storage,
+    denom: &str,
+    operation: &str,
+    is_paused: fn(&PausedOperations) -> bool,
+) -> Result<(), ContractError> {
+    let global = GLOBAL_PAUSE.may_load(storage)?.unwrap_or_default();
+    let for_denom = DENOM_PAUSE.may_load(storage, denom)?.unwrap_or_default();
+
+    if is_paused(&global) || is_paused(&for_denom) {
+        return Err(ContractError::Paused {
+            operation: operation.to_string(),
+            denom: denom.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Ensures minting `denoms` does not take any capped denom over its max supply.
+/// Amounts of the same denom within one mint are summed before checking.
+pub fn check_max_supply(
+    deps: Deps<TokenFactoryQuery>,
+    denoms: &[Coin],
+) -> Result<(), ContractError> {
+    let mut totals: BTreeMap<&str, Uint128> = BTreeMap::new();
+    for coin in denoms {
+        let total = totals.entry(coin.denom.as_str()).or_default();
+        *total = total.checked_add(coin.amount).map_err(StdError::from)?;
+    }
+
+    for (denom, amount) in totals {
+        if let Some(max_supply) = MAX_SUPPLY.may_load(deps.storage, denom)? {
+            let supply = deps
+                .querier
+                .query_supply(denom)?
+                .amount
+                .checked_add(amount)
+                .map_err(StdError::from)?;
+
+            if supply > max_supply {
+                return Err(ContractError::MaxSupplyExceeded {
+                    denom: denom.to_string(),
+                    max_supply,
+                    supply,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Errors if the minter is limited to a set of denoms and any of `denoms` is outside of it.
+pub fn ensure_minter_denoms(
+    minter: &str,
+    info: &MinterInfo,
+    denoms: &[Coin],
+) -> Result<(), ContractError> {
+    if let Some(allowed) = info.denoms.as_ref() {
+        if let Some(coin) = denoms.iter().find(|c| !allowed.contains(&c.denom)) {
+            return Err(ContractError::DenomNotAllowedForMinter {
+                minter: minter.to_string(),
+                denom: coin.denom.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Runs the checks a whitelisted minter must pass to mint `denoms`: the minter's denom scope,
+/// the mint pause state and the minter's allowances. Allowances are only spent once all pass.
+pub fn authorize_mint(
+    storage: &mut dyn Storage,
+    minter: &str,
+    info: &MinterInfo,
+    denoms: &[Coin],
+) -> Result<(), ContractError> {
+    ensure_minter_denoms(minter, info, denoms)?;
+    for coin in denoms {
+        ensure_not_paused(storage, &coin.denom, "mint", |p| p.mint)?;
+    }
+    spend_mint_allowances(storage, minter, denoms)
+}
+
+/// Decrements the minter's remaining allowance for every denom being minted.
+/// Denoms the minter has no allowance set for are not limited.
+/// Nothing is saved unless every allowance covers its amount.
+pub fn spend_mint_allowances(
+    storage: &mut dyn Storage,
+    minter: &str,
+    denoms: &[Coin],
+) -> Result<(), ContractError> {
+    let mut updates: Vec<(&str, Uint128)> = vec![];
+    for coin in denoms {
+        if let Some(remaining) = MINT_ALLOWANCES.may_load(storage, (minter, &coin.denom))? {
+            let updated = remaining.checked_sub(coin.amount).map_err(|_| {
+                ContractError::MintAllowanceExceeded {
+                    minter: minter.to_string(),
+                    denom: coin.denom.clone(),
+                    requested: coin.amount,
+                    remaining,
+                }
+            })?;
+            updates.push((&coin.denom, updated));
+        }
+    }
+
+    for (denom, updated) in updates {
+        MINT_ALLOWANCES.save(storage, (minter, denom), &updated)?;
+    }
+    Ok(())
+}
+
+/// Checks the holder consented to `burner` burning `coin` and spends that allowance.
+/// An allowance granted to the burner itself is used first. Otherwise the burner needs the
+/// Burner role, and when the denom requires consent the allowance granted to this contract.
+pub fn spend_burn_allowance(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    contract: &Addr,
+    holder: &str,
+    coin: &Coin,
+    burner: &Addr,
+    is_burner: bool,
+) -> Result<(), ContractError> {
+    let mut spender = burner.as_str();
+    if !BURN_ALLOWANCES.has(storage, (holder, &coin.denom, spender)) {
+        if !is_burner {
+            return Err(ContractError::Unauthorized {});
+        }
+        if !BURN_CONSENT_REQUIRED.has(storage, &coin.denom) {
+            return Ok(());
+        }
+        spender = contract.as_str();
+    }
+
+    let key = (holder, coin.denom.as_str(), spender);
+    let allowance =
+        BURN_ALLOWANCES
+            .may_load(storage, key)?
+            .ok_or_else(|| ContractError::NoBurnAllowance {
+                holder: holder.to_string(),
+                denom: coin.denom.clone(),
+            })?;
+
+    if let Some(expires) = allowance.expires {
+        if expires.is_expired(block) {
+            return Err(ContractError::BurnAllowanceExpired {
+                holder: holder.to_string(),
+                denom: coin.denom.clone(),
+            });
+        }
+    }
+
+    let remaining = allowance.amount.checked_sub(coin.amount).map_err(|_| {
+        ContractError::BurnAllowanceExceeded {
+            holder: holder.to_string(),
+            denom: coin.denom.clone(),
+            requested: coin.amount,
+            remaining: allowance.amount,
+        }
+    })?;
+    BURN_ALLOWANCES.save(
+        storage,
+        key,
+        &BurnAllowance {
+            amount: remaining,
+            expires: allowance.expires,
+        },
+    )?;
+    Ok(())
+}
+
+/// Spends `coin` from the allowance `owner` gave `spender`, cw20 style.
+pub fn spend_allowance(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    owner: &str,
+    spender: &str,
+    coin: &Coin,
+) -> Result<(), ContractError> {
+    let key = (owner, coin.denom.as_str(), spender);
+    let allowance =
+        ALLOWANCES
+            .may_load(storage, key)?
+            .ok_or_else(|| ContractError::NoAllowance {
+                owner: owner.to_string(),
+                denom: coin.denom.clone(),
+            })?;
+
+    if let Some(expires) = allowance.expires {
+        if expires.is_expired(block) {
+            return Err(ContractError::AllowanceExpired {
+                owner: owner.to_string(),
+                denom: coin.denom.clone(),
+            });
+        }
+    }
+
+    let remaining = allowance.amount.checked_sub(coin.amount).map_err(|_| {
+        ContractError::AllowanceExceeded {
+            owner: owner.to_string(),
+            denom: coin.denom.clone(),
+            requested: coin.amount,
+            remaining: allowance.amount,
+        }
+    })?;
+    ALLOWANCES.save(
+        storage,
+        key,
+        &Allowance {
+            amount: remaining,
+            expires: allowance.expires,
+        },
+    )?;
+    Ok(())
+}
+
+/// Gives back allowance spent on a mint which did not go through.
+pub fn refund_mint_allowances(
+    storage: &mut dyn Storage,
+    minter: &str,
+    denoms: &[Coin],
+) -> StdResult<()> {
+    for coin in denoms {
+        let key = (minter, coin.denom.as_str());
+        if let Some(remaining) = MINT_ALLOWANCES.may_load(storage, key)? {
+            MINT_ALLOWANCES.save(storage, key, &remaining.checked_add(coin.amount)?)?;
+        }
+    }
+    Ok(())
+}
+
+pub fn get_mint_allowances(storage: &dyn Storage, minter: &str) -> StdResult<Vec<Coin>> {
+    MINT_ALLOWANCES
+        .prefix(minter)
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+        .collect()
+}
+
+/// Adds minted `coins` to the running totals of the denom and of the minter at `height`.
+pub fn record_mint(
+    storage: &mut dyn Storage,
+    height: u64,
+    minter: &str,
+    coins: &[Coin],
+) -> StdResult<()> {
+    add_to_totals(storage, height, minter, coins, &MINTED, &MINTED_BY)
+}
+
+/// Adds burned `coins` to the running totals of the denom and of the burner at `height`.
+pub fn record_burn(
+    storage: &mut dyn Storage,
+    height: u64,
+    burner: &str,
+    coins: &[Coin],
+) -> StdResult<()> {
+    add_to_totals(storage, height, burner, coins, &BURNED, &BURNED_BY)
+}
+
+/// Takes `coins` back out of the mint totals of the denom and of the minter at `height`,
+/// for mints which failed after being recorded.
+pub fn unrecord_mint(
+    storage: &mut dyn Storage,
+    height: u64,
+    minter: &str,
+    coins: &[Coin],
+) -> StdResult<()> {
+    for coin in coins {
+        MINTED.update(storage, &coin.denom, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_sub(coin.amount)?)
+        })?;
+        MINTED_BY.update(
+            storage,
+            (minter, &coin.denom),
+            height,
+            |total| -> StdResult<_> { Ok(total.unwrap_or_default().checked_sub(coin.amount)?) },
+        )?;
+    }
+    Ok(())
+}
+
+fn add_to_totals(
+    storage: &mut dyn Storage,
+    height: u64,
+    address: &str,
+    coins: &[Coin],
+    totals: &SnapshotMap<&str, Uint128>,
+    address_totals: &SnapshotMap<(&str, &str), Uint128>,
+) -> StdResult<()> {
+    for coin in coins {
+        totals.update(storage, &coin.denom, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default().checked_add(coin.amount)?)
+        })?;
+        address_totals.update(
+            storage,
+            (address, &coin.denom),
+            height,
+            |total| -> StdResult<_> { Ok(total.unwrap_or_default().checked_add(coin.amount)?) },
+        )?;
+    }
+    Ok(())
+}
+
+pub fn validate_vesting_schedule(schedule: &VestingSchedule) -> Result<(), ContractError> {
+    let invalid = |message: &str| ContractError::InvalidVestingSchedule {
+        message: message.to_string(),
+    };
+
+    if schedule.duration == 0 {
+        return Err(invalid("duration must be greater than zero"));
+    }
+    if schedule.cliff > schedule.duration {
+        return Err(invalid("cliff can not be longer than the duration"));
+    }
+    if let VestingRelease::Stepped { interval } = schedule.release {
+        if interval == 0 || interval > schedule.duration {
+            return Err(invalid(
+                "interval must be greater than zero and at most the duration",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Saves a new vesting of `coin` for `recipient` and returns its id.
+/// The tokens must already be (or be about to be) minted to the contract.
+pub fn save_vesting(
+    storage: &mut dyn Storage,
+    recipient: &str,
+    coin: &Coin,
+    schedule: VestingSchedule,
+) -> StdResult<u64> {
+    let id = VESTING_COUNT.may_load(storage)?.unwrap_or_default() + 1;
+    VESTING_COUNT.save(storage, &id)?;
+
+    let vesting = Vesting {
+        recipient: recipient.to_string(),
+        denom: coin.denom.clone(),
+        total: coin.amount,
+        claimed: Uint128::zero(),
+        schedule,
+        revoked: false,
+    };
+    VESTINGS.save(storage, id, &vesting)?;
+    RECIPIENT_VESTINGS.save(storage, (recipient, id), &Empty {})?;
+
+    Ok(id)
+}
+
+/// Amount of the vesting unlocked as of `block`, including what has already been claimed.
+pub fn vested_amount(vesting: &Vesting, block: &BlockInfo) -> Uint128 {
+    if vesting.revoked {
+        return vesting.total;
+    }
+
+    let schedule = &vesting.schedule;
+    let now = match schedule.unit {
+        VestingUnit::Time => block.time.seconds(),
+        VestingUnit::Height => block.height,
+    };
+
+    if now < schedule.start.saturating_add(schedule.cliff) {
+        return Uint128::zero();
+    }
+
+    let elapsed = now - schedule.start;
+    if elapsed >= schedule.duration {
+        return vesting.total;
+    }
+
+    let elapsed = match schedule.release {
+        VestingRelease::Linear => elapsed,
+        VestingRelease::Stepped { interval } => elapsed - elapsed % interval,
+    };
+    vesting.total.multiply_ratio(elapsed, schedule.duration)
+}
+
+/// Address of the tokenfactory module account, which mints are sent from and burns are sent to.
+pub fn tokenfactory_module_address(api: &dyn Api) -> StdResult<Addr> {
+    let hash = Sha256::digest(b"tokenfactory");
+    api.addr_humanize(&CanonicalAddr::from(&hash[..20]))
+}
+
+/// Errors if either side is frozen, or the denom's transfer policy does not allow sending
+/// from `from` to `to`. Burns are always allowed.
+pub fn ensure_transfer_allowed(
+    deps: Deps<TokenFactoryQuery>,
+    contract: &Addr,
+    denom: &str,
+    from: &str,
+    to: &str,
+) -> Result<(), ContractError> {
+    // Tokens are sent to the module to be burned, so frozen accounts can still be burned from.
+    let module = tokenfactory_module_address(deps.api)?;
+    if to == module.as_str() {
+        return Ok(());
+    }
+
+    for address in [from, to] {
+        if FROZEN.has(deps.storage, (denom, address)) {
+            return Err(ContractError::Frozen {
+                address: address.to_string(),
+                denom: denom.to_string(),
+            });
+        }
+    }
+
+    let policy = TRANSFER_POLICY
+        .may_load(deps.storage, denom)?
+        .unwrap_or(TransferPolicy::Open);
+    if policy == TransferPolicy::Open {
+        return Ok(());
+    }
+
+    // Mints, burns and the contract's own transfers (burn funds, vesting claims) always pass.
+    let exempt = |address: &str| address == module.as_str() || address == contract.as_str();
+
+    let allowed = exempt(from)
+        || exempt(to)
+        || match policy {
+            TransferPolicy::Open => true,
+            TransferPolicy::Soulbound | TransferPolicy::TransfersPaused => false,
+            TransferPolicy::AllowlistOnly => [from, to]
+                .iter()
+                .all(|&a| TRANSFER_ALLOWLIST.has(deps.storage, (denom, a))),
+        };
+
+    if !allowed {
+        return Err(ContractError::TransferNotAllowed {
+            denom: denom.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            policy: policy.as_str().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// token-bindings has no message for this, so MsgSetBeforeSendHook is encoded by hand.
+/// An empty `hook` unsets it.
+pub fn set_before_send_hook_msg(
+    contract: &Addr,
+    denom: &str,
+    hook: &str,
+) -> CosmosMsg<TokenFactoryMsg> {
+    let mut value: Vec<u8> = vec![];
+    encode_proto_string(&mut value, 1, contract.as_str());
+    encode_proto_string(&mut value, 2, denom);
+    encode_proto_string(&mut value, 3, hook);
+
+    CosmosMsg::Stargate {
+        type_url: "/osmosis.tokenfactory.v1beta1.MsgSetBeforeSendHook".to_string(),
+        value: Binary::from(value),
+    }
+}
+
+fn encode_proto_string(buf: &mut Vec<u8>, field: u32, value: &str) {
+    if value.is_empty() {
+        return;
+    }
+    encode_proto_varint(buf, u64::from(field << 3 | 2));
+    encode_proto_varint(buf, value.len() as u64);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn encode_proto_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
 /// Creates the token messages to mint factory tokens to an address (from this middleware contract)
-/// If there are no denoms provided to mint (standard coins), it will return an error
+/// If there are no denoms provided to mint (standard coins), it will return an error.
+/// The denoms should already be checked with `validate_managed_coins`.
 ///
 /// You should not use this function unless you are within this contract. It is not for other contract use
 /// unless you also use TokenFactoryMsg's, which is the entire point of this contract to not have to do.
@@ -34,7 +670,6 @@ pub fn mint_factory_token_messages(
 
     let msgs: Vec<TokenFactoryMsg> = denoms
         .iter()
-        .filter(|d| denoms.iter().any(|d2| d2.denom == d.denom))
         .map(|d| TokenFactoryMsg::MintTokens {
             denom: d.denom.clone(),
             amount: d.amount,
@@ -87,3 +722,142 @@ pub fn mint_tokens_msg(address: String, denom: String, amount: Uint128) -> Token
         mint_to_address: address,
     }
 }
+
+/// Builds the messages to create `denom` under this contract and to mint its initial balances.
+/// Saves the denom's max supply when one is set. Returns the full denom alongside the messages.
+pub fn build_new_denom(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    contract: &Addr,
+    denom: NewDenom,
+) -> Result<(String, TokenFactoryMsg, Vec<TokenFactoryMsg>), ContractError> {
+    let subdenom = denom.symbol.to_lowercase();
+    let full_denom = format!("factory/{}/{}", contract, subdenom);
+
+    let initial_balances = denom.initial_balances.clone().unwrap_or_default();
+
+    // Initial balances count towards the max supply.
+    if let Some(max_supply) = denom.max_supply {
+        let mut supply = Uint128::zero();
+        for b in initial_balances.iter() {
+            supply = supply.checked_add(b.amount).map_err(StdError::from)?;
+        }
+
+        if supply > max_supply {
+            return Err(ContractError::MaxSupplyExceeded {
+                denom: full_denom,
+                max_supply,
+                supply,
+            });
+        }
+
+        MAX_SUPPLY.save(storage, &full_denom, &max_supply)?;
+    }
+
+    // Validate addresses and vesting schedules.
+    for initial in initial_balances.iter() {
+        api.addr_validate(&initial.address)?;
+        if let Some(schedule) = initial.vesting.as_ref() {
+            validate_vesting_schedule(schedule)?;
+        }
+    }
+
+    // Vesting balances are minted to the contract until claimed.
+    let mut mint_msgs: Vec<TokenFactoryMsg> = vec![];
+    for b in initial_balances {
+        match b.vesting {
+            Some(schedule) => {
+                let coin = Coin::new(b.amount.u128(), full_denom.clone());
+                save_vesting(storage, &b.address, &coin, schedule)?;
+                mint_msgs.push(mint_tokens_msg(
+                    contract.to_string(),
+                    full_denom.clone(),
+                    b.amount,
+                ));
+            }
+            None => mint_msgs.push(mint_tokens_msg(b.address, full_denom.clone(), b.amount)),
+        }
+    }
+
+    let create_msg = create_denom_msg(subdenom, full_denom.clone(), denom);
+
+    Ok((full_denom, create_msg, mint_msgs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::Timestamp;
+
+    // Starts at 1000 with a 100 cliff and runs for 1000, in seconds.
+    fn vesting(release: VestingRelease, revoked: bool) -> Vesting {
+        Vesting {
+            recipient: "juno1recipient".to_string(),
+            denom: "factory/juno1contract/test".to_string(),
+            total: Uint128::new(1000),
+            claimed: Uint128::zero(),
+            schedule: VestingSchedule {
+                unit: VestingUnit::Time,
+                start: 1000,
+                cliff: 100,
+                duration: 1000,
+                release,
+                revocable: true,
+            },
+            revoked,
+        }
+    }
+
+    fn at_time(seconds: u64) -> BlockInfo {
+        let mut block = mock_env().block;
+        block.time = Timestamp::from_seconds(seconds);
+        block
+    }
+
+    #[test]
+    fn nothing_vests_before_the_cliff() {
+        let vesting = vesting(VestingRelease::Linear, false);
+        assert_eq!(vested_amount(&vesting, &at_time(0)), Uint128::zero());
+        assert_eq!(vested_amount(&vesting, &at_time(1099)), Uint128::zero());
+        assert_eq!(vested_amount(&vesting, &at_time(1100)), Uint128::new(100));
+    }
+
+    #[test]
+    fn linear_release() {
+        let vesting = vesting(VestingRelease::Linear, false);
+        assert_eq!(vested_amount(&vesting, &at_time(1500)), Uint128::new(500));
+        assert_eq!(vested_amount(&vesting, &at_time(1999)), Uint128::new(999));
+        assert_eq!(vested_amount(&vesting, &at_time(2000)), Uint128::new(1000));
+        assert_eq!(vested_amount(&vesting, &at_time(5000)), Uint128::new(1000));
+    }
+
+    #[test]
+    fn stepped_release_rounds_down_to_the_interval() {
+        let vesting = vesting(VestingRelease::Stepped { interval: 300 }, false);
+        assert_eq!(vested_amount(&vesting, &at_time(1100)), Uint128::zero());
+        assert_eq!(vested_amount(&vesting, &at_time(1299)), Uint128::zero());
+        assert_eq!(vested_amount(&vesting, &at_time(1300)), Uint128::new(300));
+        assert_eq!(vested_amount(&vesting, &at_time(1899)), Uint128::new(600));
+        assert_eq!(vested_amount(&vesting, &at_time(2000)), Uint128::new(1000));
+    }
+
+    #[test]
+    fn height_schedules_use_the_block_height() {
+        let mut vesting = vesting(VestingRelease::Linear, false);
+        vesting.schedule.unit = VestingUnit::Height;
+
+        let mut block = at_time(5000);
+        block.height = 1500;
+        assert_eq!(vested_amount(&vesting, &block), Uint128::new(500));
+    }
+
+    #[test]
+    fn revoked_vestings_keep_what_had_vested() {
+        // RevokeVesting lowers the total to what had vested at that point.
+        let mut vesting = vesting(VestingRelease::Linear, true);
+        vesting.total = Uint128::new(400);
+        assert_eq!(vested_amount(&vesting, &at_time(0)), Uint128::new(400));
+        assert_eq!(vested_amount(&vesting, &at_time(5000)), Uint128::new(400));
+    }
+}
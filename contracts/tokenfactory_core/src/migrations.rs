@@ -0,0 +1,118 @@
+use cosmwasm_std::{StdResult, Storage};
+use semver::Version;
+
+/// Runs every state migration between `from` and the current contract version, oldest first.
+pub fn migrate_state(storage: &mut dyn Storage, from: &Version) -> StdResult<()> {
+    if *from < Version::new(0, 1, 0) {
+        v0_1_0::migrate(storage)?;
+    }
+    Ok(())
+}
+
+/// 0.1.0 moved the minter whitelist and managed denoms out of `Config` into their own maps,
+/// and made `Config.manager` optional so the manager can be renounced.
+pub mod v0_1_0 {
+    use cosmwasm_schema::cw_serde;
+    use cosmwasm_std::{Empty, StdResult, Storage};
+    use cw_storage_plus::Item;
+
+    use crate::state::{Config, MinterInfo, CONFIG, DENOMS, MINTERS};
+
+    #[cw_serde]
+    struct ConfigV0_0_4 {
+        manager: String,
+        allowed_mint_addresses: Vec<String>,
+        denoms: Vec<String>,
+    }
+
+    const CONFIG_V0_0_4: Item<ConfigV0_0_4> = Item::new("config");
+
+    pub fn migrate(storage: &mut dyn Storage) -> StdResult<()> {
+        let old = CONFIG_V0_0_4.load(storage)?;
+
+        for address in old.allowed_mint_addresses.iter() {
+            MINTERS.save(storage, address, &MinterInfo { denoms: None })?;
+        }
+        for denom in old.denoms.iter() {
+            DENOMS.save(storage, denom, &Empty {})?;
+        }
+
+        CONFIG.save(
+            storage,
+            &Config {
+                manager: Some(old.manager),
+            },
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use cosmwasm_std::testing::MockStorage;
+
+        #[test]
+        fn moves_whitelist_and_denoms_out_of_config() {
+            let mut storage = MockStorage::new();
+            CONFIG_V0_0_4
+                .save(
+                    &mut storage,
+                    &ConfigV0_0_4 {
+                        manager: "juno1manager".to_string(),
+                        allowed_mint_addresses: vec![
+                            "juno1minter".to_string(),
+                            "juno1minter2".to_string(),
+                        ],
+                        denoms: vec!["factory/juno1contract/test".to_string()],
+                    },
+                )
+                .unwrap();
+
+            migrate(&mut storage).unwrap();
+
+            assert_eq!(
+                CONFIG.load(&storage).unwrap(),
+                Config {
+                    manager: Some("juno1manager".to_string())
+                }
+            );
+            for minter in ["juno1minter", "juno1minter2"] {
+                assert_eq!(
+                    MINTERS.load(&storage, minter).unwrap(),
+                    MinterInfo { denoms: None }
+                );
+            }
+            assert!(DENOMS.has(&storage, "factory/juno1contract/test"));
+        }
+
+        #[test]
+        fn runs_from_older_versions_only() {
+            let mut storage = MockStorage::new();
+            CONFIG_V0_0_4
+                .save(
+                    &mut storage,
+                    &ConfigV0_0_4 {
+                        manager: "juno1manager".to_string(),
+                        allowed_mint_addresses: vec![],
+                        denoms: vec![],
+                    },
+                )
+                .unwrap();
+
+            // Already on 0.1.0, the stored config is left as it is and fails to load as the new one.
+            crate::migrations::migrate_state(&mut storage, &semver::Version::new(0, 1, 0)).unwrap();
+            assert!(CONFIG.load(&storage).is_err());
+
+            crate::migrations::migrate_state(&mut storage, &semver::Version::new(0, 0, 4)).unwrap();
+            assert_eq!(
+                CONFIG.load(&storage).unwrap().manager,
+                Some("juno1manager".to_string())
+            );
+        }
+
+        #[test]
+        fn fails_without_a_stored_config() {
+            let mut storage = MockStorage::new();
+            assert!(migrate(&mut storage).is_err());
+        }
+    }
+}
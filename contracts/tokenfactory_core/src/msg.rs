@@ -14,22 +14,46 @@ pub struct InstantiateMsg {
 }
 
 #[cw_serde]
-pub struct NewDenom {
-    pub name: String,
-    pub description: Option<String>,
-    pub symbol: String,
-    pub decimals: u32,
-    pub initial_balances: Option<Vec<InitialBalance>>,
+pub struct MigrateMsg {}
+
+// Called by the tokenfactory module on transfers of denoms which have this contract as
+// their before-send hook
+#[cw_serde]
+pub enum SudoMsg {
+    BlockBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
+    TrackBeforeSend {
+        from: String,
+        to: String,
+        amount: Coin,
+    },
 }
 
+// The only cw4 group query used, to check minter group membership
 #[cw_serde]
-pub struct InitialBalance {
-    pub address: String,
-    pub amount: Uint128,
+pub enum Cw4QueryMsg {
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+}
+
+#[cw_serde]
+pub struct Cw4MemberResponse {
+    pub weight: Option<u64>,
 }
 
-use cosmwasm_std::{Coin, Uint128};
-pub use juno_tokenfactory_types::msg::ExecuteMsg;
+use cosmwasm_std::{Coin, Timestamp, Uint128};
+use cw_utils::Expiration;
+pub use juno_tokenfactory_types::msg::{
+    ExecuteMsg, HookExecuteMsg, InitialBalance, MintBatchEntry, MintBatchMode, MintReceiveMsg,
+    MintReceiverExecuteMsg, NewDenom, PausedOperations, Role, TimelockedAction, TokenHookMsg,
+    TokenOperation, TransferPolicy, VestingRelease, VestingSchedule, VestingUnit,
+};
+use token_bindings::Metadata;
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -42,4 +66,324 @@ pub enum QueryMsg {
 
     #[returns(Vec<Coin>)]
     GetAllBalances { address: String },
+
+    // Total supply of the denom from bank
+    #[returns(Coin)]
+    GetSupply { denom: String },
+
+    // Current tokenfactory admin of the denom
+    #[returns(String)]
+    GetDenomAdmin { denom: String },
+
+    #[returns(Option<Metadata>)]
+    GetDenomMetadata { denom: String },
+
+    // Supply, admin and metadata of the denom, along with the caps and pause state this
+    // contract keeps for it. Minters are listed with GetAllMintAllowances.
+    #[returns(DenomInfoResponse)]
+    GetDenomInfo { denom: String },
+
+    // Amounts minted and burned through this contract. height defaults to the latest state,
+    // otherwise the totals are as they were at the start of that block.
+    #[returns(TotalsResponse)]
+    GetDenomTotals { denom: String, height: Option<u64> },
+
+    // Amounts the address minted and burned through this contract
+    #[returns(TotalsResponse)]
+    GetAddressTotals {
+        address: String,
+        denom: String,
+        height: Option<u64>,
+    },
+
+    // Addresses holding the role, not including the manager
+    #[returns(Vec<String>)]
+    GetRoleMembers { role: Role },
+
+    #[returns(Vec<Role>)]
+    GetRoles { address: String },
+
+    // Global pause state, plus the denom's own when one is given
+    #[returns(PauseStateResponse)]
+    GetPauseState { denom: Option<String> },
+
+    #[returns(Option<crate::state::PendingManager>)]
+    GetPendingManager {},
+
+    #[returns(Option<Uint128>)]
+    GetMaxSupply { denom: String },
+
+    // None if the address is not whitelisted, directly or through the minter group
+    #[returns(Option<crate::state::MinterInfo>)]
+    GetMinter { address: String },
+
+    #[returns(Option<crate::state::MinterGroup>)]
+    GetMinterGroup {},
+
+    // Privileged actions, oldest first. Filtered by action (such as "force_transfer") and/or denom.
+    #[returns(Vec<AuditEntryResponse>)]
+    AuditLog {
+        action: Option<String>,
+        denom: Option<String>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    // Zero when the owner has not approved the spender
+    #[returns(AllowanceResponse)]
+    GetAllowance {
+        owner: String,
+        spender: String,
+        denom: String,
+    },
+
+    // Allowances the owner has given, paginated by (denom, spender)
+    #[returns(Vec<AllowanceInfo>)]
+    ListAllowances {
+        owner: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    #[returns(bool)]
+    IsBurnConsentRequired { denom: String },
+
+    // `burner` defaults to this contract, the allowance any Burner can spend
+    #[returns(Option<crate::state::BurnAllowance>)]
+    GetBurnAllowance {
+        holder: String,
+        denom: String,
+        burner: Option<String>,
+    },
+
+    // Allowances the holder has granted, paginated by (denom, burner)
+    #[returns(Vec<BurnAllowanceResponse>)]
+    ListBurnAllowances {
+        holder: String,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    // Global subscribers, or the subscribers of `denom` when set
+    #[returns(Vec<HookResponse>)]
+    ListHooks {
+        denom: Option<String>,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(bool)]
+    IsFrozen { denom: String, address: String },
+
+    #[returns(TransferPolicy)]
+    GetTransferPolicy { denom: String },
+
+    #[returns(bool)]
+    IsTransferAllowlisted { denom: String, address: String },
+
+    #[returns(Vec<String>)]
+    ListTransferAllowlist {
+        denom: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<String>)]
+    ListFrozen {
+        denom: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<MinterResponse>)]
+    ListMinters {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    #[returns(Vec<String>)]
+    ListDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Registered denoms in the page which this contract is no longer the admin of
+    #[returns(Vec<UncontrolledDenomResponse>)]
+    ReconcileDenoms {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Remaining amount of each denom the address can mint
+    #[returns(Vec<Coin>)]
+    GetMintAllowances { address: String },
+
+    // Remaining allowances of every whitelisted address
+    #[returns(Vec<MintAllowancesResponse>)]
+    GetAllMintAllowances {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    // Delay of every timelocked action which has one
+    #[returns(Vec<TimelockResponse>)]
+    GetTimelocks {},
+
+    #[returns(Option<String>)]
+    GetGuardian {},
+
+    #[returns(Vec<PendingActionResponse>)]
+    ListPendingActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    // None when the manager is not held by signers
+    #[returns(Option<crate::state::Signers>)]
+    GetSigners {},
+
+    // Executed proposals are removed, expired ones are kept with `expired` set
+    #[returns(Option<ProposalResponse>)]
+    GetProposal { id: u64 },
+
+    #[returns(Vec<ProposalResponse>)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+
+    // None once the vesting is fully claimed
+    #[returns(Option<VestingResponse>)]
+    GetVesting { id: u64 },
+
+    #[returns(Vec<VestingResponse>)]
+    ListVestings {
+        recipient: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+}
+
+#[cw_serde]
+pub struct DenomInfoResponse {
+    pub denom: String,
+    // false when the denom is not registered with this contract
+    pub managed: bool,
+    pub supply: Uint128,
+    pub max_supply: Option<Uint128>,
+    pub admin: String,
+    pub metadata: Option<Metadata>,
+    // cw4 group whose members can mint every managed denom
+    pub minter_group: Option<String>,
+    pub paused: PauseStateResponse,
+    pub transfer_policy: TransferPolicy,
+}
+
+#[cw_serde]
+pub struct TotalsResponse {
+    pub minted: Uint128,
+    pub burned: Uint128,
+}
+
+#[cw_serde]
+pub struct MinterResponse {
+    pub address: String,
+    pub denoms: Option<Vec<String>>,
+}
+
+#[cw_serde]
+pub struct PauseStateResponse {
+    pub global: PausedOperations,
+    pub denom: Option<PausedOperations>,
+}
+
+#[cw_serde]
+pub struct MintAllowancesResponse {
+    pub address: String,
+    pub allowances: Vec<Coin>,
+}
+
+// vested includes the claimed amount, so vested - claimed is what can be claimed now
+#[cw_serde]
+pub struct VestingResponse {
+    pub id: u64,
+    pub recipient: String,
+    pub denom: String,
+    pub total: Uint128,
+    pub claimed: Uint128,
+    pub vested: Uint128,
+    pub revoked: bool,
+    pub schedule: VestingSchedule,
+}
+
+#[cw_serde]
+pub struct TimelockResponse {
+    pub action: TimelockedAction,
+    pub delay: u64,
+}
+
+#[cw_serde]
+pub struct PendingActionResponse {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    pub proposer: String,
+    pub eta: Timestamp,
+}
+
+#[cw_serde]
+pub struct ProposalResponse {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    pub proposer: String,
+    pub approvals: Vec<String>,
+    pub expires: Timestamp,
+    pub expired: bool,
+}
+
+#[cw_serde]
+pub struct HookResponse {
+    pub contract: String,
+    pub tolerate_errors: bool,
+}
+
+#[cw_serde]
+pub struct UncontrolledDenomResponse {
+    pub denom: String,
+    pub admin: String,
+}
+
+#[cw_serde]
+pub struct BurnAllowanceResponse {
+    pub denom: String,
+    pub burner: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct AllowanceResponse {
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct AllowanceInfo {
+    pub denom: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct AuditEntryResponse {
+    pub id: u64,
+    pub action: String,
+    pub sender: String,
+    pub denoms: Vec<String>,
+    pub reason: Option<String>,
+    pub msg: ExecuteMsg,
+    // Set when a queued action ran, to the id it was queued with
+    pub pending_action: Option<u64>,
+    pub height: u64,
+    pub time: Timestamp,
 }
@@ -1,11 +1,223 @@
 use cosmwasm_schema::cw_serde;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Coin, Empty, Timestamp, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotMap, Strategy};
+use cw_utils::Expiration;
+use juno_tokenfactory_types::msg::{ExecuteMsg, PausedOperations, TransferPolicy, VestingSchedule};
 
 #[cw_serde]
 pub struct Config {
-    pub manager: String,
-    pub allowed_mint_addresses: Vec<String>,
-    pub denoms: Vec<String>,
+    // None once the manager has been renounced
+    pub manager: Option<String>,
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+
+#[cw_serde]
+pub struct MinterInfo {
+    // the only denoms the minter may mint, every managed denom when None
+    pub denoms: Option<Vec<String>>,
+}
+
+// address -> minter, for every address allowed to mint
+pub const MINTERS: Map<&str, MinterInfo> = Map::new("minters");
+
+// every denom managed by this contract
+pub const DENOMS: Map<&str, Empty> = Map::new("denoms");
+
+// cw4 group whose members are whitelisted alongside MINTERS
+#[cw_serde]
+pub struct MinterGroup {
+    pub address: String,
+    pub min_weight: Option<u64>,
+}
+
+pub const MINTER_GROUP: Item<MinterGroup> = Item::new("minter_group");
+
+#[cw_serde]
+pub struct PendingManager {
+    pub address: String,
+    pub expiry: Option<Expiration>,
+}
+
+pub const PENDING_MANAGER: Item<PendingManager> = Item::new("pending_manager");
+
+// (role, address) -> set when the address holds the role
+pub const ROLES: Map<(&str, &str), Empty> = Map::new("roles");
+
+// operations paused for every denom
+pub const GLOBAL_PAUSE: Item<PausedOperations> = Item::new("global_pause");
+// denom -> operations paused for only that denom
+pub const DENOM_PAUSE: Map<&str, PausedOperations> = Map::new("denom_pause");
+
+// denom -> the total supply mints through this contract may never exceed
+pub const MAX_SUPPLY: Map<&str, Uint128> = Map::new("max_supply");
+
+// (minter, denom) -> amount the minter can still mint of that denom
+pub const MINT_ALLOWANCES: Map<(&str, &str), Uint128> = Map::new("mint_allowances");
+
+// Running totals of what passed through this contract, snapshotted every block they change.
+// denom -> amount minted through this contract
+pub const MINTED: SnapshotMap<&str, Uint128> = SnapshotMap::new(
+    "minted",
+    "minted__checkpoints",
+    "minted__changelog",
+    Strategy::EveryBlock,
+);
+// denom -> amount burned through this contract
+pub const BURNED: SnapshotMap<&str, Uint128> = SnapshotMap::new(
+    "burned",
+    "burned__checkpoints",
+    "burned__changelog",
+    Strategy::EveryBlock,
+);
+// (address, denom) -> amount the address minted through this contract
+pub const MINTED_BY: SnapshotMap<(&str, &str), Uint128> = SnapshotMap::new(
+    "minted_by",
+    "minted_by__checkpoints",
+    "minted_by__changelog",
+    Strategy::EveryBlock,
+);
+// (address, denom) -> amount the address burned through this contract
+pub const BURNED_BY: SnapshotMap<(&str, &str), Uint128> = SnapshotMap::new(
+    "burned_by",
+    "burned_by__checkpoints",
+    "burned_by__changelog",
+    Strategy::EveryBlock,
+);
+
+// Mints from best-effort MintBatches waiting on their submessage reply, in the order the replies
+// arrive. A batch started while another is waiting goes in front of it.
+#[cw_serde]
+pub struct PendingBatchMint {
+    pub index: u64,
+    pub minter: String,
+    pub address: String,
+    pub coin: Coin,
+}
+
+pub const PENDING_BATCH_MINTS: Item<Vec<PendingBatchMint>> = Item::new("pending_batch_mints");
+
+// Tokens held by the contract and released to `recipient` over `schedule`
+#[cw_serde]
+pub struct Vesting {
+    pub recipient: String,
+    pub denom: String,
+    pub total: Uint128,
+    pub claimed: Uint128,
+    pub schedule: VestingSchedule,
+    // Once revoked, `total` is only what had vested at that point
+    pub revoked: bool,
+}
+
+pub const VESTING_COUNT: Item<u64> = Item::new("vesting_count");
+pub const VESTINGS: Map<u64, Vesting> = Map::new("vestings");
+// (recipient, vesting id)
+pub const RECIPIENT_VESTINGS: Map<(&str, u64), Empty> = Map::new("recipient_vestings");
+
+// Delay in seconds of each timelocked action, keyed by TimelockedAction::as_str
+pub const TIMELOCKS: Map<&str, u64> = Map::new("timelocks");
+pub const GUARDIAN: Item<String> = Item::new("guardian");
+
+// An action requested while its timelock was set, runnable by anyone from `eta`
+#[cw_serde]
+pub struct PendingAction {
+    pub msg: ExecuteMsg,
+    pub proposer: String,
+    pub eta: Timestamp,
+}
+
+pub const PENDING_ACTION_COUNT: Item<u64> = Item::new("pending_action_count");
+pub const PENDING_ACTIONS: Map<u64, PendingAction> = Map::new("pending_actions");
+
+// Set while the manager is held by signers, the contract is then its own manager
+#[cw_serde]
+pub struct Signers {
+    pub addresses: Vec<String>,
+    pub threshold: u32,
+    // Seconds a proposal stays open for
+    pub proposal_duration: u64,
+}
+
+pub const SIGNERS: Item<Signers> = Item::new("signers");
+
+#[cw_serde]
+pub struct Proposal {
+    pub msg: ExecuteMsg,
+    pub proposer: String,
+    pub approvals: Vec<String>,
+    pub expires: Timestamp,
+}
+
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+
+// (denom, address) pairs blocked by the before-send hook
+pub const FROZEN: Map<(&str, &str), Empty> = Map::new("frozen");
+
+// Denoms without a policy are Open
+pub const TRANSFER_POLICY: Map<&str, TransferPolicy> = Map::new("transfer_policy");
+// (denom, address) pairs allowed to transfer under TransferPolicy::AllowlistOnly
+pub const TRANSFER_ALLOWLIST: Map<(&str, &str), Empty> = Map::new("transfer_allowlist");
+
+#[cw_serde]
+pub struct HookSubscription {
+    pub tolerate_errors: bool,
+}
+
+// Subscribers to every denom, keyed by contract
+pub const GLOBAL_HOOKS: Map<&str, HookSubscription> = Map::new("global_hooks");
+// (denom, contract)
+pub const DENOM_HOOKS: Map<(&str, &str), HookSubscription> = Map::new("denom_hooks");
+
+#[cw_serde]
+pub struct BurnAllowance {
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+}
+
+// (holder, denom, burner). The burner is this contract for allowances any Burner can spend.
+pub const BURN_ALLOWANCES: Map<(&str, &str, &str), BurnAllowance> = Map::new("burn_allowances");
+// Denoms whose BurnFrom needs a burn allowance from the holder
+pub const BURN_CONSENT_REQUIRED: Map<&str, Empty> = Map::new("burn_consent_required");
+
+// cw20 style allowances spent with TransferFrom
+#[cw_serde]
+pub struct Allowance {
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+}
+
+// (owner, denom, spender)
+pub const ALLOWANCES: Map<(&str, &str, &str), Allowance> = Map::new("allowances");
+
+// Append only log of privileged actions
+#[cw_serde]
+pub struct AuditEntry {
+    // snake_case name of the ExecuteMsg variant
+    pub action: String,
+    pub sender: String,
+    pub denoms: Vec<String>,
+    pub reason: Option<String>,
+    pub msg: ExecuteMsg,
+    // Set when a queued action ran, to the id it was queued with
+    pub pending_action: Option<u64>,
+    pub height: u64,
+    pub time: Timestamp,
+}
+
+pub const AUDIT_COUNT: Item<u64> = Item::new("audit_count");
+pub const AUDIT_LOG: Map<u64, AuditEntry> = Map::new("audit_log");
+// (action, id) and (denom, id) indexes into the log
+pub const AUDIT_BY_ACTION: Map<(&str, u64), Empty> = Map::new("audit_by_action");
+pub const AUDIT_BY_DENOM: Map<(&str, u64), Empty> = Map::new("audit_by_denom");
+
+// Set while a ForceTransfer sent by this contract runs, so the before-send hook lets it through
+// even when an account is frozen. Cleared in the reply.
+#[cw_serde]
+pub struct ForcedTransfer {
+    pub denom: String,
+    pub from: String,
+    pub to: String,
+}
+
+pub const FORCED_TRANSFER: Item<ForcedTransfer> = Item::new("forced_transfer");
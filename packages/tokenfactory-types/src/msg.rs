@@ -1,6 +1,7 @@
 use cosmwasm_schema::cw_serde;
 
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Binary, Coin, Uint128};
+use cw_utils::Expiration;
 // use token_bindings::Metadata;
 use token_bindings::Metadata;
 
@@ -8,6 +9,43 @@ use token_bindings::Metadata;
 pub enum ExecuteMsg {
     // == ANYONE ==
     Burn {},
+    // Sends the sender every unlocked amount of their vestings. All of them when `ids` is not set.
+    ClaimVested {
+        ids: Option<Vec<u64>>,
+    },
+    // Runs a queued action once its delay has passed
+    ExecutePendingAction {
+        id: u64,
+    },
+    // Lets `burner` (or any Burner through this contract, when not set) burn up to `amount`
+    // of the sender's tokens with BurnFrom. Replaces any previous allowance.
+    SetBurnAllowance {
+        denom: String,
+        burner: Option<String>,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    RevokeBurnAllowance {
+        denom: String,
+        burner: Option<String>,
+    },
+    // cw20 style allowances, letting `spender` move the sender's tokens with TransferFrom
+    IncreaseAllowance {
+        spender: String,
+        denom: Coin,
+        expires: Option<Expiration>,
+    },
+    DecreaseAllowance {
+        spender: String,
+        denom: Coin,
+        expires: Option<Expiration>,
+    },
+    // Moves tokens `owner` approved the sender to spend, through ForceTransfer
+    TransferFrom {
+        owner: String,
+        recipient: String,
+        denom: Coin,
+    },
 
     // == WHITELIST ==
     // Mints actual tokens to an address (only whitelisted addresses can do this)
@@ -15,42 +53,469 @@ pub enum ExecuteMsg {
         address: String,
         denom: Vec<Coin>,
     },
+    // Mints the tokens to the core contract, then sends them to `contract` along with `msg`,
+    // as a `mint_receive` message (like cw20's Send)
+    MintAndCall {
+        contract: String,
+        denom: Vec<Coin>,
+        msg: Binary,
+    },
+    // Mints to many recipients at once. Defaults to `AllOrNothing`.
+    MintBatch {
+        mints: Vec<MintBatchEntry>,
+        mode: Option<MintBatchMode>,
+    },
+    // Mints tokens to the contract, to be released to `recipient` over the schedule.
+    MintVesting {
+        recipient: String,
+        denom: Coin,
+        schedule: VestingSchedule,
+    },
 
     // == MANAGER ==
+    // Each of these takes an optional `reason`, kept with the action in the audit log
     BurnFrom {
         from: String,
         denom: Coin,
+        reason: Option<String>,
     },
 
     TransferAdmin {
         denom: String,
         new_address: String,
+        reason: Option<String>,
     },
 
     ForceTransfer {
         from: String,
         to: String,
         denom: Coin,
+        reason: Option<String>,
     },
 
     SetMetadata {
         denom: String,
         metadata: Metadata,
+        reason: Option<String>,
     },
 
     // Could be a DAO, normal contract, or CW4
-    // Future: should we specify what name/denom an address can mint?
+    // When `denoms` is set the addresses can only mint those denoms, otherwise they can mint all.
+    // Re-adding an address replaces its denoms.
     AddWhitelist {
         addresses: Vec<String>,
+        denoms: Option<Vec<String>>,
+        reason: Option<String>,
     },
     RemoveWhitelist {
         addresses: Vec<String>,
+        reason: Option<String>,
+    },
+    // Also treats members of a cw4 group as whitelisted, when their weight is at least
+    // `min_weight`. Group members can mint every denom. None stops using the group.
+    SetMinterGroup {
+        address: Option<String>,
+        min_weight: Option<u64>,
+        reason: Option<String>,
+    },
+
+    // Sets how much of each denom a whitelisted address has left to mint.
+    // Denoms without an allowance are not limited for that address.
+    SetMintAllowances {
+        address: String,
+        allowances: Vec<Coin>,
+        reason: Option<String>,
+    },
+    // Leaves the address nothing to mint of the denoms, or lifts their limit when `unlimited`
+    RemoveMintAllowances {
+        address: String,
+        denoms: Vec<String>,
+        #[serde(default)]
+        unlimited: bool,
+        reason: Option<String>,
     },
 
+    // The contract must already be the tokenfactory admin of each denom
     AddDenom {
         denoms: Vec<String>,
+        reason: Option<String>,
     },
     RemoveDenom {
         denoms: Vec<String>,
+        reason: Option<String>,
+    },
+
+    // Creates a new denom owned by this contract and starts managing it.
+    // Any denom creation fee the chain charges must be sent along, the rest is refunded.
+    CreateDenom {
+        denom: NewDenom,
+        reason: Option<String>,
+    },
+
+    // Caps the total supply of a managed denom. None removes the cap.
+    SetMaxSupply {
+        denom: String,
+        max_supply: Option<Uint128>,
+        reason: Option<String>,
+    },
+
+    // Pauses or resumes operations for every denom, or only for `denom` when set
+    SetPaused {
+        denom: Option<String>,
+        paused: PausedOperations,
+        reason: Option<String>,
+    },
+
+    // Frozen addresses can not send or receive the denom while its before-send hook is set
+    Freeze {
+        denom: String,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+    Unfreeze {
+        denom: String,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+    // Registers this contract as the denom's before-send hook, or unsets it
+    SetBeforeSendHook {
+        denom: String,
+        enabled: bool,
+        reason: Option<String>,
+    },
+    // When required, BurnFrom can only burn what holders allowed with SetBurnAllowance.
+    // Once turned on for a denom it can not be turned off.
+    SetBurnConsentRequired {
+        denom: String,
+        required: bool,
+        reason: Option<String>,
+    },
+    // Subscribes a contract to TokenHookMsg callbacks for one denom, or for every denom.
+    // Failed callbacks are ignored when `tolerate_errors` is set, otherwise they revert the action.
+    AddHook {
+        contract: String,
+        denom: Option<String>,
+        tolerate_errors: bool,
+        reason: Option<String>,
+    },
+    RemoveHook {
+        contract: String,
+        denom: Option<String>,
+        reason: Option<String>,
+    },
+    // Only enforced while the denom's before-send hook is set
+    SetTransferPolicy {
+        denom: String,
+        policy: TransferPolicy,
+        reason: Option<String>,
+    },
+    // Accounts which can send and receive the denom under `AllowlistOnly`
+    AddTransferAllowlist {
+        denom: String,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+    RemoveTransferAllowlist {
+        denom: String,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+
+    // Roles let other addresses run a subset of the manager actions.
+    // Only the manager can grant or revoke SuperAdmin.
+    GrantRole {
+        role: Role,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+    RevokeRole {
+        role: Role,
+        addresses: Vec<String>,
+        reason: Option<String>,
+    },
+
+    // Stops a revocable vesting. What has vested stays claimable, the rest is burned.
+    RevokeVesting {
+        id: u64,
+        reason: Option<String>,
+    },
+
+    // Starts handing the contract over to a new manager, who must accept it before `expiry`
+    ProposeManager {
+        address: String,
+        expiry: Option<Expiration>,
+        reason: Option<String>,
+    },
+    CancelManagerProposal {
+        reason: Option<String>,
+    },
+    // Removes the manager forever, along with every granted role. Every manager action is
+    // disabled afterwards.
+    RenounceManager {
+        reason: Option<String>,
+    },
+
+    // Delay in seconds before the action runs once requested. None removes the delay.
+    // Lowering or removing a delay is itself queued behind the current delay.
+    SetTimelock {
+        action: TimelockedAction,
+        delay: Option<u64>,
+        reason: Option<String>,
+    },
+    // The guardian can cancel queued actions, alongside the manager
+    SetGuardian {
+        address: Option<String>,
+        reason: Option<String>,
+    },
+    CancelPendingAction {
+        id: u64,
+        reason: Option<String>,
+    },
+
+    // Hands the manager over to the signers. The contract becomes its own manager and any
+    // manager action then has to be proposed and approved by `threshold` of the signers.
+    // Proposals expire after `proposal_duration` seconds. Granted roles are suspended meanwhile.
+    SetSigners {
+        signers: Vec<String>,
+        threshold: u32,
+        proposal_duration: u64,
+        reason: Option<String>,
     },
+
+    // == SIGNERS ==
+    // Proposes running `msg` as the manager. Counts as the proposer's approval.
+    Propose {
+        msg: Box<ExecuteMsg>,
+    },
+    // Runs the proposal once it has enough approvals
+    ApproveProposal {
+        id: u64,
+    },
+
+    // == PENDING MANAGER ==
+    AcceptManager {},
+}
+
+#[cw_serde]
+pub struct NewDenom {
+    pub name: String,
+    pub description: Option<String>,
+    pub symbol: String,
+    pub decimals: u32,
+    pub initial_balances: Option<Vec<InitialBalance>>,
+    pub max_supply: Option<Uint128>,
+}
+
+#[cw_serde]
+pub struct MintBatchEntry {
+    pub address: String,
+    pub denom: Vec<Coin>,
+}
+
+#[cw_serde]
+pub enum MintBatchMode {
+    // Any invalid entry fails the whole batch.
+    AllOrNothing,
+    // Invalid entries are skipped. Each mint is sent as a submessage and failures are
+    // reported in the reply events, with the minter's allowance and totals restored.
+    BestEffort,
+}
+
+#[cw_serde]
+pub struct InitialBalance {
+    pub address: String,
+    pub amount: Uint128,
+    // When set, the amount is held by the contract and released over the schedule.
+    pub vesting: Option<VestingSchedule>,
+}
+
+// Nothing unlocks before `start + cliff`. After that the unlocked share grows with the time
+// (or blocks) since `start`, until everything is unlocked at `start + duration`.
+#[cw_serde]
+pub struct VestingSchedule {
+    pub unit: VestingUnit,
+    pub start: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub release: VestingRelease,
+    // Whether the manager can revoke the unvested amount
+    #[serde(default)]
+    pub revocable: bool,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum VestingUnit {
+    // `start`, `cliff` and `duration` are in seconds
+    Time,
+    // `start`, `cliff` and `duration` are in blocks
+    Height,
+}
+
+#[cw_serde]
+pub enum VestingRelease {
+    // Unlocks continuously
+    Linear,
+    // Unlocks once every `interval`
+    Stepped { interval: u64 },
+}
+
+// Operations which can be paused by the manager. Each is toggled separately.
+#[cw_serde]
+#[derive(Default)]
+pub struct PausedOperations {
+    #[serde(default)]
+    pub mint: bool,
+    #[serde(default)]
+    pub burn: bool,
+    #[serde(default)]
+    pub burn_from: bool,
+    #[serde(default)]
+    pub force_transfer: bool,
+    #[serde(default)]
+    pub transfer_from: bool,
+}
+
+// Each role unlocks a group of manager actions. The manager implicitly holds every role.
+#[cw_serde]
+#[derive(Copy)]
+pub enum Role {
+    // AddWhitelist, RemoveWhitelist, SetMinterGroup, SetMintAllowances, RemoveMintAllowances
+    MinterAdmin,
+    // BurnFrom, SetBurnConsentRequired is SuperAdmin only
+    Burner,
+    // SetMetadata
+    MetadataEditor,
+    // ForceTransfer
+    ClawbackOperator,
+    // SetPaused
+    Pauser,
+    // Freeze, Unfreeze
+    Freezer,
+    // Every role above, plus denom management, TransferAdmin, granting roles, RevokeVesting
+    // and SetBeforeSendHook, SetTransferPolicy, the transfer allowlist and hooks
+    SuperAdmin,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::MinterAdmin => "minter_admin",
+            Role::Burner => "burner",
+            Role::MetadataEditor => "metadata_editor",
+            Role::ClawbackOperator => "clawback_operator",
+            Role::Pauser => "pauser",
+            Role::Freezer => "freezer",
+            Role::SuperAdmin => "super_admin",
+        }
+    }
+
+    pub fn all() -> [Role; 7] {
+        [
+            Role::MinterAdmin,
+            Role::Burner,
+            Role::MetadataEditor,
+            Role::ClawbackOperator,
+            Role::Pauser,
+            Role::Freezer,
+            Role::SuperAdmin,
+        ]
+    }
+}
+
+// Actions which can be delayed so holders have time to react before they happen
+#[cw_serde]
+#[derive(Copy)]
+pub enum TimelockedAction {
+    ForceTransfer,
+    BurnFrom,
+    TransferAdmin,
+    RemoveDenom,
+}
+
+impl TimelockedAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimelockedAction::ForceTransfer => "force_transfer",
+            TimelockedAction::BurnFrom => "burn_from",
+            TimelockedAction::TransferAdmin => "transfer_admin",
+            TimelockedAction::RemoveDenom => "remove_denom",
+        }
+    }
+
+    pub fn all() -> [TimelockedAction; 4] {
+        [
+            TimelockedAction::ForceTransfer,
+            TimelockedAction::BurnFrom,
+            TimelockedAction::TransferAdmin,
+            TimelockedAction::RemoveDenom,
+        ]
+    }
+}
+
+// Which transfers of a denom the before-send hook lets through. Minting, burning and
+// transfers to or from this contract are never blocked by the policy.
+#[cw_serde]
+#[derive(Copy)]
+pub enum TransferPolicy {
+    Open,
+    // Tokens can not be transferred, only minted and burned
+    Soulbound,
+    // Only transfers between accounts on the denom's transfer allowlist
+    AllowlistOnly,
+    TransfersPaused,
+}
+
+impl TransferPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferPolicy::Open => "open",
+            TransferPolicy::Soulbound => "soulbound",
+            TransferPolicy::AllowlistOnly => "allowlist_only",
+            TransferPolicy::TransfersPaused => "transfers_paused",
+        }
+    }
+}
+
+// Sent to hook subscribers, which need a matching `token_hook` variant in their ExecuteMsg
+#[cw_serde]
+pub enum HookExecuteMsg {
+    TokenHook(TokenHookMsg),
+}
+
+#[cw_serde]
+pub struct TokenHookMsg {
+    pub operation: TokenOperation,
+    pub denom: String,
+    pub amount: Uint128,
+    // Not set for mints
+    pub from: Option<String>,
+    // Not set for burns
+    pub recipient: Option<String>,
+    // The minter, burner or clawback operator who made the change
+    pub sender: String,
+}
+
+#[cw_serde]
+#[derive(Copy)]
+pub enum TokenOperation {
+    Mint,
+    Burn,
+    BurnFrom,
+    ForceTransfer,
+    TransferFrom,
+}
+
+// Sent by MintAndCall with the minted tokens attached as funds. Receiving contracts need a
+// matching `mint_receive` variant in their ExecuteMsg.
+#[cw_serde]
+pub enum MintReceiverExecuteMsg {
+    MintReceive(MintReceiveMsg),
+}
+
+#[cw_serde]
+pub struct MintReceiveMsg {
+    // The minter who called MintAndCall
+    pub sender: String,
+    pub msg: Binary,
 }
@@ -34,21 +34,32 @@ func TestBasicContract(t *testing.T) {
 	assert.Equal(t, uaddr, denomAdmin)
 
 	// Setup TokenFactory Core contract (mints on yours/contracts behalf) where uaddr can mint for anyone
-	tfCoreMsg := fmt.Sprintf(`{"allowed_mint_addresses":["%s"],"existing_denoms":["%s"]}`, uaddr, tfDenom)
+	tfCoreMsg := fmt.Sprintf(`{"allowed_mint_addresses":["%s"]}`, uaddr)
 	tfCoreCodeId, tfCoreContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), TF_CORE_FILE, tfCoreMsg)
 
 	assert.Assert(t, len(tfCoreContractAddr) > 0)
-	res := GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.AllowedMintAddresses) == 1)
-	assert.Equal(t, res.Data.Denoms[0], tfDenom)
+	minters := GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Assert(t, len(minters) == 1)
+
+	// the denom can not be registered until the contract is its admin
+	msg := fmt.Sprintf(`{"add_denom":{"denoms":["%s"]}}`, tfDenom)
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err == nil {
+		t.Fatal("add_denom should fail before the admin is transferred")
+	}
 
 	// transfer admin to the contract
 	helpers.TransferTokenFactoryAdmin(t, ctx, juno, user, tfCoreContractAddr, tfDenom)
 	denomAdmin = helpers.GetTokenFactoryAdmin(t, ctx, juno, tfDenom)
 	assert.Equal(t, tfCoreContractAddr, denomAdmin)
 
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err != nil {
+		t.Fatal(err)
+	}
+	denoms := GetContractDenoms(t, ctx, juno, tfCoreContractAddr)
+	assert.Equal(t, denoms[0], tfDenom)
+
 	// Mint 100 tokens to user through the tfCore contract
-	msg := fmt.Sprintf(`{"mint":{"address":"%s","denom":[{"denom":"%s","amount":"100"}]}}`, uaddr, tfDenom)
+	msg = fmt.Sprintf(`{"mint":{"address":"%s","denom":[{"denom":"%s","amount":"100"}]}}`, uaddr, tfDenom)
 	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err != nil {
 		t.Fatal(err)
 	}
@@ -67,8 +78,8 @@ func TestBasicContract(t *testing.T) {
 	}
 
 	// still is one
-	res = GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.AllowedMintAddresses) == 1)
+	minters = GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Assert(t, len(minters) == 1)
 
 	// add a diff user
 	msg = fmt.Sprintf(`{"add_whitelist":{"addresses":["%s"]}}`, uaddr2)
@@ -76,8 +87,8 @@ func TestBasicContract(t *testing.T) {
 		t.Fatal(err)
 	}
 
-	res = GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.AllowedMintAddresses) == 2)
+	minters = GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Assert(t, len(minters) == 2)
 
 	// remove user2 from whitelist
 	msg = fmt.Sprintf(`{"remove_whitelist":{"addresses":["%s"]}}`, uaddr2)
@@ -85,8 +96,8 @@ func TestBasicContract(t *testing.T) {
 		t.Fatal(err)
 	}
 
-	res = GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.AllowedMintAddresses) == 1)
+	minters = GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Assert(t, len(minters) == 1)
 
 	// force transfer 1 token from user to user2
 	msg = fmt.Sprintf(`{"force_transfer":{"from":"%s","to":"%s","denom":{"denom":"%s","amount":"3"}}}`, uaddr, uaddr2, tfDenom)
@@ -113,23 +124,20 @@ func TestBasicContract(t *testing.T) {
 	assert.Equal(t, uaddr2, denomAdmin)
 
 	// DENOM WHITELIST
-	// adds a denom (Only allow factory/ in the future?)
+	// only factory denoms the contract is the admin of can be added
 	msg = fmt.Sprintf(`{"add_denom":{"denoms":["%s"]}}`, "randomdenom")
-	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err != nil {
-		t.Fatal(err)
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err == nil {
+		t.Fatal("add_denom should fail for a non factory denom")
 	}
 
-	res = GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.Denoms) == 1)
-
-	// Remove denom
-	msg = fmt.Sprintf(`{"remove_denom":{"denoms":["%s"]}}`, "randomdenom")
-	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err != nil {
-		t.Fatal(err)
+	// the contract handed its admin to uaddr2
+	msg = fmt.Sprintf(`{"add_denom":{"denoms":["%s"]}}`, tfDenom)
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, msg); err == nil {
+		t.Fatal("add_denom should fail for a denom the contract is not the admin of")
 	}
 
-	res = GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Assert(t, len(res.Data.Denoms) == 0)
+	denoms = GetContractDenoms(t, ctx, juno, tfCoreContractAddr)
+	assert.Assert(t, len(denoms) == 0)
 
 	// Create denom on instantiation
 	tfMsg := fmt.Sprintf(`{"allowed_mint_addresses":["%s"],"new_denoms":[{"name":"new","description":"desc","symbol":"crt","decimals":6,"initial_balances":[{"address":"%s","amount":"420"}]}]}`, uaddr, uaddr)
@@ -146,9 +154,9 @@ func TestBasicContract(t *testing.T) {
 
 	tfCreatedDenom := fmt.Sprintf(`factory/%s/crt`, tfCoreAddr)
 
-	res = GetContractConfig(t, ctx, juno, tfCoreAddr)
-	assert.Equal(t, len(res.Data.Denoms), 1)
-	assert.Equal(t, res.Data.Denoms[0], tfCreatedDenom)
+	denoms = GetContractDenoms(t, ctx, juno, tfCoreAddr)
+	assert.Equal(t, len(denoms), 1)
+	assert.Equal(t, denoms[0], tfCreatedDenom)
 
 	// Validate admin.
 	createdDenomAdmin := helpers.GetTokenFactoryAdmin(t, ctx, juno, tfCreatedDenom)
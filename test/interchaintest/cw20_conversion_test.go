@@ -36,7 +36,7 @@ func TestCw20ConversionMigrateContract(t *testing.T) {
 	_, cw20ContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), "../../base_artifacts/cw20_base.wasm", cw20Msg)
 
 	// Tokenfactory Core minter
-	tfCoreMsg := fmt.Sprintf(`{"allowed_mint_addresses":[],"existing_denoms":["%s"]}`, tfDenom)
+	tfCoreMsg := `{"allowed_mint_addresses":[]}`
 	_, tfCoreContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), TF_CORE_FILE, tfCoreMsg)
 
 	// transfer admin to the contract
@@ -44,6 +44,12 @@ func TestCw20ConversionMigrateContract(t *testing.T) {
 	denomAdmin = helpers.GetTokenFactoryAdmin(t, ctx, juno, tfDenom)
 	assert.Equal(t, tfCoreContractAddr, denomAdmin)
 
+	// register the denom now that the contract is its admin
+	addDenomMsg := fmt.Sprintf(`{"add_denom":{"denoms":["%s"]}}`, tfDenom)
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, addDenomMsg); err != nil {
+		t.Fatal(err)
+	}
+
 	// conversion migrate contract (1 CW20 -> contract -> burn CW20 and mint 1 tf denom)
 	migrateCW20Msg := fmt.Sprintf(`{"cw20_token_address":"%s","contract_minter_address":"%s","tf_denom":"%s"}`, cw20ContractAddr, tfCoreContractAddr, tfDenom)
 	_, cw20MigrateContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), MIGRATE_FILE, migrateCW20Msg)
@@ -55,9 +61,10 @@ func TestCw20ConversionMigrateContract(t *testing.T) {
 	}
 
 	// Ensure the contract config data is set correctly.
-	res := GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Equal(t, res.Data.AllowedMintAddresses[0], cw20MigrateContractAddr)
-	assert.Equal(t, res.Data.Denoms[0], tfDenom)
+	minters := GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Equal(t, minters[0], cw20MigrateContractAddr)
+	denoms := GetContractDenoms(t, ctx, juno, tfCoreContractAddr)
+	assert.Equal(t, denoms[0], tfDenom)
 
 	// actual CW20 testing on the contract
 	// ensure user has 0 tf denom balance
@@ -40,13 +40,19 @@ func TestNativeConversionMigrateContract(t *testing.T) {
 	assert.Equal(t, uaddr, helpers.GetTokenFactoryAdmin(t, ctx, juno, tfDenom))
 
 	// Tokenfactory Core minter
-	tfCoreMsg := fmt.Sprintf(`{"allowed_mint_addresses":[],"existing_denoms":["%s"]}`, tfDenom)
+	tfCoreMsg := `{"allowed_mint_addresses":[]}`
 	_, tfCoreContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), TF_CORE_FILE, tfCoreMsg)
 
 	// transfer admin to the contract
 	helpers.TransferTokenFactoryAdmin(t, ctx, juno, user, tfCoreContractAddr, tfDenom)
 	assert.Equal(t, tfCoreContractAddr, helpers.GetTokenFactoryAdmin(t, ctx, juno, tfDenom))
 
+	// register the denom now that the contract is its admin
+	addDenomMsg := fmt.Sprintf(`{"add_denom":{"denoms":["%s"]}}`, tfDenom)
+	if _, err := juno.ExecuteContract(ctx, user.KeyName(), tfCoreContractAddr, addDenomMsg); err != nil {
+		t.Fatal(err)
+	}
+
 	// conversion migrate contract (1 native -> 1 tf denom)
 	migrateNativeMsg := fmt.Sprintf(`{"burn_denom":"%s","contract_minter_address":"%s","tf_denom":"%s"}`, nativeDenom, tfCoreContractAddr, tfDenom)
 	_, naitveMigrateContractAddr := helpers.SetupContract(t, ctx, juno, user.KeyName(), MIGRATE_FILE, migrateNativeMsg)
@@ -58,9 +64,10 @@ func TestNativeConversionMigrateContract(t *testing.T) {
 	}
 
 	// Ensure the contract config data is set correctly.
-	res := GetContractConfig(t, ctx, juno, tfCoreContractAddr)
-	assert.Equal(t, res.Data.AllowedMintAddresses[0], naitveMigrateContractAddr)
-	assert.Equal(t, res.Data.Denoms[0], tfDenom)
+	minters := GetContractMinters(t, ctx, juno, tfCoreContractAddr)
+	assert.Equal(t, minters[0], naitveMigrateContractAddr)
+	denoms := GetContractDenoms(t, ctx, juno, tfCoreContractAddr)
+	assert.Equal(t, denoms[0], tfDenom)
 
 	// ensure user has 0 tf denom balance
 	AssertBalance(t, ctx, juno, uaddr, tfDenom, 0)
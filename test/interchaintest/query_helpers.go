@@ -27,6 +27,27 @@ func GetContractConfig(t *testing.T, ctx context.Context, chain *cosmos.CosmosCh
 	return cRes
 }
 
+func GetContractMinters(t *testing.T, ctx context.Context, chain *cosmos.CosmosChain, contract string) []string {
+	var mRes ListMintersResponse
+	err := chain.QueryContract(ctx, contract, QueryMsg{ListMinters: &ListPage{}}, &mRes)
+	require.NoError(t, err)
+
+	minters := make([]string, 0, len(mRes.Data))
+	for _, m := range mRes.Data {
+		minters = append(minters, m.Address)
+	}
+	t.Log("GetContractMinters", minters)
+	return minters
+}
+
+func GetContractDenoms(t *testing.T, ctx context.Context, chain *cosmos.CosmosChain, contract string) []string {
+	var dRes ListDenomsResponse
+	err := chain.QueryContract(ctx, contract, QueryMsg{ListDenoms: &ListPage{}}, &dRes)
+	require.NoError(t, err)
+	t.Log("GetContractDenoms", dRes.Data)
+	return dRes.Data
+}
+
 // TokenFactory Core contract Queries
 func GetCoreContractUserBalance(t *testing.T, ctx context.Context, chain *cosmos.CosmosChain, contract, uaddr, tfDenom string) GetBalanceResponse {
 	var bRes GetBalanceResponse
@@ -12,6 +12,8 @@ type QueryMsg struct {
 	GetConfig      *struct{}       `json:"get_config,omitempty"`
 	GetBalance     *GetBalance     `json:"get_balance,omitempty"`
 	GetAllBalances *GetAllBalances `json:"get_all_balances,omitempty"`
+	ListMinters    *ListPage       `json:"list_minters,omitempty"`
+	ListDenoms     *ListPage       `json:"list_denoms,omitempty"`
 }
 
 // entry helpers
@@ -24,6 +26,11 @@ type GetAllBalances struct {
 	Address string `json:"address"`
 }
 
+type ListPage struct {
+	StartAfter *string `json:"start_after,omitempty"`
+	Limit      *uint32 `json:"limit,omitempty"`
+}
+
 // Response Types (json is always 'data' from the chain return value)
 type GetConfigResponse struct {
 	Data *ConfigTfCore `json:"data"`
@@ -37,6 +44,14 @@ type GetAllBalancesResponse struct {
 	Data []Coin `json:"data"`
 }
 
+type ListMintersResponse struct {
+	Data []Minter `json:"data"`
+}
+
+type ListDenomsResponse struct {
+	Data []string `json:"data"`
+}
+
 // type WhitelistResponse struct {
 // 	Data []string `json:"data"`
 // }
@@ -48,9 +63,12 @@ type GetAllBalancesResponse struct {
 
 // Base Data Types
 type ConfigTfCore struct {
-	Manager              string   `json:"manager"`
-	AllowedMintAddresses []string `json:"allowed_mint_addresses"`
-	Denoms               []string `json:"denoms"`
+	Manager string `json:"manager"`
+}
+
+type Minter struct {
+	Address string   `json:"address"`
+	Denoms  []string `json:"denoms"`
 }
 
 type Coin struct {